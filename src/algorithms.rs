@@ -0,0 +1,1694 @@
+//!
+//! Limb-agnostic algorithms shared by all Uint and FixedUint implementations.
+//!
+//! Numbers are slices of limbs in little-endian order. Every function is
+//! generic over the `Limb` type and only uses the constant time primitives
+//! from `CTimeOperations` (and the limb's `Wide` type for multiplication).
+//!
+//! The `*_slice` functions work on pre-allocated buffers and never allocate.
+//! The `*_generic` functions allocate the result and call the `*_slice`
+//! functions. Temporary buffers are `Scratch` vectors, which are zeroized
+//! when they are dropped.
+//!
+//! Only the limb values are secret. The number of limbs is public, so the
+//! functions branch and index on slice lengths freely.
+//!
+
+use std::cmp::{max, min};
+use std::fmt;
+use std::mem::{self, MaybeUninit};
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+use std::sync::atomic::{compiler_fence, Ordering};
+
+use base::UintError;
+use util::*;
+
+// ===================== STRINGS ===========================
+
+/// Return -1 (all ones) if `lo <= c <= hi`, 0 otherwise.
+#[inline]
+fn range_mask(c: i32, lo: i32, hi: i32) -> i32 {
+    // Both differences are negative iff c is in the range.
+    ((lo - 1 - c) & (c - hi - 1)) >> 31
+}
+
+/// Return `(value, valid)` for a digit in base `radix <= 36` without
+/// branching on it. `valid` is 1 for `[0-9a-zA-Z]` with a value smaller than
+/// `radix` and 0 otherwise, `value` is 0 if invalid.
+#[inline]
+fn digit_value(c: u8, radix: u32) -> (u64, u64) {
+    let c = i32::from(c);
+    let digit = range_mask(c, 0x30, 0x39);
+    // Setting 0x20 maps 'A'-'Z' to 'a'-'z'.
+    let lower = c | 0x20;
+    let alpha = range_mask(lower, 0x61, 0x7a);
+    let value = (digit & (c - 0x30)) | (alpha & (lower - 0x61 + 10));
+    let valid = digit | alpha;
+    let valid = valid & range_mask(value, 0, radix as i32 - 1);
+    ((value & valid) as u64, (valid & 1) as u64)
+}
+
+/// Return the lower case character for the digit `n < 36` without branching
+/// on it.
+#[inline]
+fn digit_char(n: u64) -> u8 {
+    let n = n as i32;
+    // Skip from '9' + 1 to 'a' if n > 9.
+    let alpha = range_mask(n, 10, 35);
+    (0x30 + n + (alpha & (0x61 - 0x3a))) as u8
+}
+
+/// Return the digits without leading zeros, but at least one digit.
+/// This looks at all digits.
+fn trim_zeros(digits: &[u8]) -> &[u8] {
+    let mut zeros = 0usize;
+    let mut leading = 1usize;
+    for &c in digits.iter() {
+        leading &= (range_mask(i32::from(c), 0x30, 0x30) & 1) as usize;
+        zeros += leading;
+    }
+    &digits[min(zeros, digits.len().saturating_sub(1))..]
+}
+
+/// Check that `s` is not empty and only has digits in base `radix`.
+/// Fails with the offset of the first invalid digit otherwise.
+///
+/// This looks at all characters, whether the string is valid is not
+/// considered secret.
+fn check_digits(s: &[u8], radix: u32) -> Result<(), UintError> {
+    if s.is_empty() {
+        return Err(UintError::EmptyString);
+    }
+    let mut valid = 1usize;
+    let mut offset = 0usize;
+    for (i, c) in s.iter().enumerate() {
+        let v = digit_value(*c, radix).1 as usize;
+        // Remember i if this is the first invalid digit.
+        let first = valid & (v ^ 1);
+        offset |= first.wrapping_neg() & i;
+        valid &= v;
+    }
+    if valid == 0 {
+        return Err(UintError::InvalidDigit(offset));
+    }
+    Ok(())
+}
+
+/// Add `prefix_len` to the offset of an `InvalidDigit` error, such that it
+/// refers to the string including its prefix.
+pub(crate) fn with_prefix_offset(e: UintError, prefix_len: usize) -> UintError {
+    match e {
+        UintError::InvalidDigit(offset) => UintError::InvalidDigit(offset + prefix_len),
+        e => e,
+    }
+}
+
+/// Split off a `0x`, `0o` or `0b` prefix and return the remaining digits and
+/// the radix. Strings without prefix are decimal.
+pub(crate) fn split_radix_prefix(s: &str) -> (&str, u32) {
+    if let Some(x) = s.strip_prefix("0x") {
+        (x, 16)
+    } else if let Some(x) = s.strip_prefix("0o") {
+        (x, 8)
+    } else if let Some(x) = s.strip_prefix("0b") {
+        (x, 2)
+    } else {
+        (s, 10)
+    }
+}
+
+/// Number of limbs needed for `len` digits in base `radix`.
+fn radix_limbs<T: Limb>(len: usize, radix: u32) -> usize {
+    let bits_per_digit = (32 - (radix - 1).leading_zeros()) as usize;
+    (len * bits_per_digit).div_ceil(T::BITS)
+}
+
+/// Panic if `radix` is not in `2..=36`.
+pub(crate) fn assert_radix(radix: u32) {
+    assert!(
+        (2..=36).contains(&radix),
+        "radix must be in the range [2, 36], got {}",
+        radix
+    );
+}
+
+/// Read the digits `s` in base `radix` into `r`.
+///
+/// This takes time depending only on the length of the string and `r`.
+/// Whether the string is valid and fits into `r` is not considered secret.
+pub(crate) fn parse_radix_slice<T: Limb>(
+    s: &[u8],
+    radix: u32,
+    r: &mut [T],
+) -> Result<(), UintError> {
+    check_digits(s, radix)?;
+    for d in r.iter_mut() {
+        *d = T::ZERO;
+    }
+    let mut overflow = T::ZERO;
+    let radix_limb = T::from_u64(u64::from(radix));
+    for c in s.iter() {
+        let digit = digit_value(*c, radix).0;
+        // r = r * radix + digit
+        let mut carry = T::from_u64(digit);
+        for d in r.iter_mut() {
+            let (lo, hi) = T::mul_add_carry(d, &radix_limb, &carry, &T::ZERO);
+            *d = lo;
+            carry = hi;
+        }
+        overflow = overflow | carry;
+    }
+
+    if overflow != T::ZERO {
+        return Err(UintError::Overflow);
+    }
+    Ok(())
+}
+
+/// `a = a / d` with a single limb `0 < d < 2^(BITS - 1)`.
+/// Returns the remainder.
+fn div_rem_small_slice<T: Limb>(a: &mut [T], d: T) -> T {
+    let mut rem = T::ZERO;
+    for limb in a.iter_mut().rev() {
+        let mut q = T::ZERO;
+        for k in (0..T::BITS).rev() {
+            // rem < d, so this doesn't overflow.
+            rem = (rem << 1) | ((*limb >> k) & T::ONE);
+            let gte = T::gte(&rem, &d);
+            rem = T::csub(&rem, &d, &gte).0;
+            q = q | (gte << k);
+        }
+        *limb = q;
+    }
+    rem
+}
+
+/// Write limbs as digits in base `radix` without prefix, e.g. `1234`.
+///
+/// The digits are computed in constant time, but the length of the string
+/// reveals the magnitude of the value.
+pub(crate) fn to_radix_string<T: Limb>(a: &[T], radix: u32) -> String {
+    // At least floor(log2(radix)) bits are consumed per digit.
+    let bits_per_digit = (31 - radix.leading_zeros()) as usize;
+    let mut digits = vec![b'0'; (a.len() * T::BITS).div_ceil(bits_per_digit)];
+    let mut q = Scratch::from_slice(a);
+    let radix = T::from_u64(u64::from(radix));
+    for c in digits.iter_mut().rev() {
+        *c = digit_char(T::to_u64(div_rem_small_slice(&mut q, radix)));
+    }
+    if digits.is_empty() {
+        digits.push(b'0');
+    }
+    let res = String::from_utf8(trim_zeros(&digits).to_vec()).unwrap();
+    zeroize_vec(&mut digits);
+    res
+}
+
+/// Read a hex string without prefix into the limbs `r`.
+/// Fails with `UintError::Overflow` if the value doesn't fit into `r`.
+fn hex_to_slice<T: Limb>(x: &[u8], r: &mut [T]) -> Result<(), UintError> {
+    check_digits(x, 16)?;
+
+    let chars_per_limb = T::BITS / 4;
+    let mut excess = T::ZERO;
+    for d in r.iter_mut() {
+        *d = T::ZERO;
+    }
+    for (i, chunk) in x.rchunks(chars_per_limb).enumerate() {
+        let mut limb = T::ZERO;
+        for c in chunk.iter() {
+            let nibble = digit_value(*c, 16).0;
+            limb = (limb << 4) | T::from_u64(nibble);
+        }
+        if i < r.len() {
+            r[i] = limb;
+        } else {
+            excess = excess | limb;
+        }
+    }
+
+    if excess != T::ZERO {
+        return Err(UintError::Overflow);
+    }
+    Ok(())
+}
+
+/// Read a hex string without prefix into as many limbs as needed for its
+/// length.
+fn hex_to_limbs<T: Limb>(x: &[u8]) -> Result<Vec<T>, UintError> {
+    let mut res = vec![T::ZERO; x.len().div_ceil(T::BITS / 4)];
+    hex_to_slice(x, &mut res)?;
+    Ok(res)
+}
+
+/// Read a hex string of the form "0xdeadbeef" into limbs.
+///
+/// This takes time depending only on the length of the string. Whether
+/// the string is valid is not considered secret.
+pub(crate) fn encode_hex<T: Limb>(s: &str) -> Result<Vec<T>, UintError> {
+    match s.strip_prefix("0x") {
+        Some(x) => hex_to_limbs(x.as_bytes()).map_err(|e| with_prefix_offset(e, 2)),
+        None => Err(UintError::MissingPrefix),
+    }
+}
+
+/// Read a hex string of the form "0xdeadbeef" into the limbs `r`.
+/// Fails with `UintError::Overflow` if the value doesn't fit into `r`.
+///
+/// This takes time depending only on the length of the string and `r`.
+pub(crate) fn encode_hex_slice<T: Limb>(s: &str, r: &mut [T]) -> Result<(), UintError> {
+    match s.strip_prefix("0x") {
+        Some(x) => hex_to_slice(x.as_bytes(), r).map_err(|e| with_prefix_offset(e, 2)),
+        None => Err(UintError::MissingPrefix),
+    }
+}
+
+/// Read a string in base `radix` without prefix into as many limbs as
+/// needed for its length.
+pub(crate) fn encode_radix<T: Limb>(s: &str, radix: u32) -> Result<Vec<T>, UintError> {
+    assert_radix(radix);
+    // This is ok, the radix is not sensitive.
+    if radix == 16 {
+        return hex_to_limbs(s.as_bytes());
+    }
+    let mut res = vec![T::ZERO; radix_limbs::<T>(s.len(), radix)];
+    parse_radix_slice(s.as_bytes(), radix, &mut res)?;
+    Ok(res)
+}
+
+/// Write all limbs as hex digits, most significant first, without prefix.
+fn hex_digits<T: Limb>(a: &[T]) -> Vec<u8> {
+    let chars_per_limb = T::BITS / 4;
+    let mut res = Vec::with_capacity(a.len() * chars_per_limb);
+    for d in a.iter().rev() {
+        for k in (0..chars_per_limb).rev() {
+            let nibble = (*d >> (4 * k)) & T::from_u64(0xf);
+            res.push(digit_char(T::to_u64(nibble)));
+        }
+    }
+    res
+}
+
+/// Write limbs as a hex string of the form `0xdeadbeef`.
+/// Zero is written as `0x0`.
+///
+/// The digits are computed in constant time, but the length of the string
+/// reveals the magnitude of the value. Use `decode_hex_padded` to avoid that.
+pub(crate) fn decode_hex<T: Limb>(a: &[T]) -> String {
+    let mut digits = hex_digits(a);
+    if digits.is_empty() {
+        digits.push(b'0');
+    }
+    let trimmed = trim_zeros(&digits);
+
+    let mut res = String::with_capacity(2 + trimmed.len());
+    res.push_str("0x");
+    res.push_str(std::str::from_utf8(trimmed).unwrap());
+    zeroize_vec(&mut digits);
+    res
+}
+
+/// Write limbs as a hex string of the form `0x0000beef` with all leading
+/// zeros, i.e. `BITS / 4` digits per limb.
+/// This takes time depending only on the number of limbs.
+pub(crate) fn decode_hex_padded<T: Limb>(a: &[T]) -> String {
+    let mut digits = hex_digits(a);
+    let mut res = String::with_capacity(2 + digits.len());
+    res.push_str("0x");
+    res.push_str(std::str::from_utf8(&digits).unwrap());
+    zeroize_vec(&mut digits);
+    res
+}
+
+/// Write limbs in base `radix` to `f`, respecting width, fill, alignment and
+/// the `#` flag like the primitive integers do.
+pub(crate) fn fmt_radix<T: Limb>(
+    a: &[T],
+    radix: u32,
+    upper: bool,
+    prefix: &str,
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    // This is ok, the radix is not sensitive.
+    let (mut digits, skip) = if radix == 16 {
+        (decode_hex(a), 2)
+    } else {
+        (to_radix_string(a, radix), 0)
+    };
+    if upper {
+        digits.make_ascii_uppercase();
+    }
+    let res = f.pad_integral(true, prefix, &digits[skip..]);
+    zeroize_string(&mut digits);
+    res
+}
+
+// ===================== BYTES ===========================
+
+/// Read little-endian `bytes` into the limbs `r`.
+/// Returns 0 if all bytes fit into `r`, and something else otherwise.
+pub(crate) fn limbs_from_le_bytes<'a, T, I>(bytes: I, r: &mut [T]) -> u8
+where
+    T: Limb,
+    I: Iterator<Item = &'a u8>,
+{
+    let bytes_per_limb = T::BITS / 8;
+    for d in r.iter_mut() {
+        *d = T::ZERO;
+    }
+    let mut excess = 0u8;
+    for (i, b) in bytes.enumerate() {
+        let limb = i / bytes_per_limb;
+        if limb < r.len() {
+            r[limb] = r[limb] | (T::from_u64(u64::from(*b)) << (8 * (i % bytes_per_limb)));
+        } else {
+            excess |= *b;
+        }
+    }
+    excess
+}
+
+/// Write the limbs `a` as little-endian bytes into `r`.
+/// Returns 0 if the value fits into `r`, and something else otherwise.
+pub(crate) fn limbs_to_le_bytes<T: Limb>(a: &[T], r: &mut [u8]) -> u8 {
+    let bytes_per_limb = T::BITS / 8;
+    let mut excess = 0u8;
+    for i in 0..max(r.len(), a.len() * bytes_per_limb) {
+        let limb = i / bytes_per_limb;
+        let b = if limb < a.len() {
+            T::to_u64(a[limb] >> (8 * (i % bytes_per_limb))) as u8
+        } else {
+            0
+        };
+        if i < r.len() {
+            r[i] = b;
+        } else {
+            excess |= b;
+        }
+    }
+    excess
+}
+
+// ===================== ZEROIZE ===========================
+
+/// Overwrite `a` with zeros. This works on limbs as well as on bytes.
+///
+/// The writes are volatile and followed by a compiler fence, so they are not
+/// optimised away even if `a` is never read again.
+pub(crate) fn zeroize_slice<T: Copy + Default>(a: &mut [T]) {
+    for d in a.iter_mut() {
+        // Safety: `d` is a valid, aligned reference.
+        unsafe { ptr::write_volatile(d, T::default()) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Overwrite the whole allocation of `a` with zeros, including the spare
+/// capacity, which may hold limbs from before a `truncate` or `clear`.
+/// The length of `a` is not changed.
+pub(crate) fn zeroize_vec<T: Copy + Default>(a: &mut Vec<T>) {
+    zeroize_slice(a);
+    for d in a.spare_capacity_mut().iter_mut() {
+        // Safety: `d` is valid and aligned, it's only not initialised.
+        unsafe { ptr::write_volatile(d, MaybeUninit::new(T::default())) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Overwrite the whole allocation of `s` with zeros, like `zeroize_vec`.
+/// This leaves a string of NUL characters.
+pub(crate) fn zeroize_string(s: &mut String) {
+    // Safety: NUL bytes are valid UTF-8.
+    zeroize_vec(unsafe { s.as_mut_vec() });
+}
+
+/// Resize `a` to `len` limbs, zero-extending or truncating it.
+///
+/// Unlike `Vec::resize`, this doesn't leave limbs behind in memory, neither
+/// in a reallocated buffer nor in the truncated part.
+pub(crate) fn resize_vec<T: Limb>(a: &mut Vec<T>, len: usize) {
+    if len > a.capacity() {
+        let mut r = Vec::with_capacity(len);
+        r.extend_from_slice(a);
+        zeroize_vec(a);
+        *a = r;
+    } else if len < a.len() {
+        zeroize_slice(&mut a[len..]);
+    }
+    a.resize(len, T::ZERO);
+}
+
+/// A temporary buffer of limbs that is zeroized when it is dropped.
+///
+/// It can't grow, so the buffer is never reallocated.
+pub(crate) struct Scratch<T: Limb>(Vec<T>);
+
+impl<T: Limb> Scratch<T> {
+    /// A zero buffer with `len` limbs.
+    pub(crate) fn new(len: usize) -> Self {
+        Scratch(vec![T::ZERO; len])
+    }
+
+    /// A buffer with a copy of `a`.
+    pub(crate) fn from_slice(a: &[T]) -> Self {
+        Scratch(a.to_vec())
+    }
+
+    /// Take the limbs out of the buffer without zeroizing them.
+    pub(crate) fn into_vec(mut self) -> Vec<T> {
+        mem::take(&mut self.0)
+    }
+}
+
+impl<T: Limb> Deref for Scratch<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T: Limb> DerefMut for Scratch<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.0
+    }
+}
+
+impl<T: Limb> Drop for Scratch<T> {
+    fn drop(&mut self) {
+        zeroize_vec(&mut self.0);
+    }
+}
+
+// ===================== SLICES ===========================
+
+/// Return 0 - c, i.e. all ones if `c == 1` and all zeroes if `c == 0`.
+#[inline]
+fn mask<T: Limb>(c: T) -> T {
+    T::add_with_carry(&!c, &T::ONE).0
+}
+
+/// `a += b` with `b.len() <= a.len()`.
+/// Returns the carry.
+pub(crate) fn add_assign_slice<T: Limb>(a: &mut [T], b: &[T]) -> T {
+    let mut carry = T::ZERO;
+    for i in 0..a.len() {
+        let bi = if i < b.len() { b[i] } else { T::ZERO };
+        let (r, c1) = T::add_with_carry(&a[i], &bi);
+        let (r, c2) = T::add_with_carry(&r, &carry);
+        a[i] = r;
+        carry = c1 | c2;
+    }
+    carry
+}
+
+/// `a -= b` if `c == 1`, `a` is left untouched if `c == 0`.
+/// `b.len()` MUST be smaller or equal to `a.len()`.
+/// Returns the borrow, which is always 0 if `c == 0`.
+pub(crate) fn csub_assign_slice<T: Limb>(a: &mut [T], b: &[T], c: T) -> T {
+    let mut borrow = T::ZERO;
+    for i in 0..a.len() {
+        let bi = if i < b.len() { b[i] } else { T::ZERO };
+        let (d, b1) = T::csub(&a[i], &bi, &c);
+        let (d, b2) = T::sub_with_borrow(&d, &borrow);
+        a[i] = d;
+        borrow = b1 | b2;
+    }
+    borrow
+}
+
+/// `a -= b` with `b.len() <= a.len()`.
+/// Returns the borrow, i.e. 1 if `a < b` and 0 otherwise.
+pub(crate) fn sub_assign_slice<T: Limb>(a: &mut [T], b: &[T]) -> T {
+    csub_assign_slice(a, b, T::ONE)
+}
+
+/// Return 1 if `a == b`, 0 otherwise.
+/// The shorter input is padded with zeroes, so `[5]` and `[5, 0]` are equal.
+pub(crate) fn eq_slice<T: Limb>(a: &[T], b: &[T]) -> T {
+    let mut eq = T::ONE;
+    for i in 0..max(a.len(), b.len()) {
+        let ai = if i < a.len() { a[i] } else { T::ZERO };
+        let bi = if i < b.len() { b[i] } else { T::ZERO };
+        eq = eq & T::equal(&ai, &bi);
+    }
+    eq
+}
+
+/// Return 1 if `a >= b`, 0 otherwise.
+/// The shorter input is padded with zeroes.
+pub(crate) fn gte_slice<T: Limb>(a: &[T], b: &[T]) -> T {
+    // Going up from the least significant limb, a differing limb decides,
+    // equal limbs keep the result of the lower limbs.
+    let mut gte = T::ONE;
+    for i in 0..max(a.len(), b.len()) {
+        let ai = if i < a.len() { a[i] } else { T::ZERO };
+        let bi = if i < b.len() { b[i] } else { T::ZERO };
+        let eq = T::equal(&ai, &bi);
+        gte = (eq & gte) | ((eq ^ T::ONE) & T::gte(&ai, &bi));
+    }
+    gte
+}
+
+/// Return 1 if `a < b`, 0 otherwise.
+/// The shorter input is padded with zeroes.
+pub(crate) fn lt_slice<T: Limb>(a: &[T], b: &[T]) -> T {
+    gte_slice(a, b) ^ T::ONE
+}
+
+/// Swap `a` and `b` if `c == 1`, leave them untouched if `c == 0`.
+/// `a` and `b` MUST have the same length.
+pub(crate) fn cswap_slice<T: Limb>(a: &mut [T], b: &mut [T], c: T) {
+    assert_eq!(a.len(), b.len(), "cswap needs operands of the same length");
+    for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+        let (s, t) = T::cswap(x, y, &c);
+        *x = s;
+        *y = t;
+    }
+}
+
+/// `a = b` if `c == 1`, `a` is left untouched if `c == 0`.
+/// `b.len()` MUST be smaller or equal to `a.len()`, `b` is padded with zeroes.
+pub(crate) fn cassign_slice<T: Limb>(a: &mut [T], b: &[T], c: T) {
+    for i in 0..a.len() {
+        let bi = if i < b.len() { b[i] } else { T::ZERO };
+        a[i] = T::cswap(&a[i], &bi, &c).0;
+    }
+}
+
+/// Shift `a` one bit to the left and shift `bit` (0 or 1) in at the bottom.
+/// Returns the bit shifted out at the top.
+fn shl1_slice<T: Limb>(a: &mut [T], bit: T) -> T {
+    let mut carry = bit;
+    for d in a.iter_mut() {
+        let next = *d >> (T::BITS - 1);
+        *d = (*d << 1) | carry;
+        carry = next;
+    }
+    carry
+}
+
+/// `res = a * b mod 2^(BITS * res.len())`
+/// Limbs of the product that don't fit into `res` are never computed.
+pub(crate) fn mul_slice<T: Limb>(a: &[T], b: &[T], res: &mut [T]) {
+    for d in res.iter_mut() {
+        *d = T::ZERO;
+    }
+    for (i, ai) in a.iter().enumerate().take(res.len()) {
+        let mut carry = T::ZERO;
+        let inner_end = min(b.len(), res.len() - i);
+        for (j, bj) in b.iter().enumerate().take(inner_end) {
+            // (higher, lower) = res[i+j] + ai * bj + carry
+            let (lower, higher) = T::mul_add_carry(ai, bj, &res[i + j], &carry);
+            res[i + j] = lower;
+            carry = higher;
+        }
+        if i + inner_end < res.len() {
+            res[i + inner_end] = carry;
+        }
+    }
+}
+
+/// `res = a * a mod 2^(BITS * res.len())`
+///
+/// Every cross product `a[i] * a[j]` with `i < j` is computed once, the sum
+/// is doubled with a shift and the squares `a[i] * a[i]` are added on top.
+/// This needs about half the limb multiplications of `mul_slice(a, a, res)`.
+/// Limbs of the square that don't fit into `res` are never computed.
+pub(crate) fn sqr_slice<T: Limb>(a: &[T], res: &mut [T]) {
+    for d in res.iter_mut() {
+        *d = T::ZERO;
+    }
+    for (i, ai) in a.iter().enumerate().take(res.len()) {
+        let mut carry = T::ZERO;
+        let inner_end = min(a.len(), res.len() - i);
+        for (j, aj) in a.iter().enumerate().take(inner_end).skip(i + 1) {
+            // (higher, lower) = res[i+j] + ai * aj + carry
+            let (lower, higher) = T::mul_add_carry(ai, aj, &res[i + j], &carry);
+            res[i + j] = lower;
+            carry = higher;
+        }
+        if i + inner_end < res.len() {
+            res[i + inner_end] = carry;
+        }
+    }
+
+    // The doubled cross products are smaller than the square, so nothing
+    // is shifted out of a result with 2 * a.len() limbs.
+    shl1_slice(res, T::ZERO);
+    let mut carry = T::ZERO;
+    for (i, ai) in a.iter().enumerate() {
+        let (lower, higher) = T::mul_with_carry(ai, ai);
+        for (k, d) in [lower, higher].iter().enumerate() {
+            if 2 * i + k < res.len() {
+                let (r, c1) = T::add_with_carry(&res[2 * i + k], d);
+                let (r, c2) = T::add_with_carry(&r, &carry);
+                res[2 * i + k] = r;
+                carry = c1 | c2;
+            }
+        }
+    }
+}
+
+/// `r = a % b` with `r.len() == b.len()`.
+///
+/// This is a bit-serial shift-and-subtract reduction. The bits of `a` are
+/// shifted into the remainder starting with the most significant one and `b`
+/// is subtracted whenever the remainder is not smaller than `b`.
+/// The comparison is always computed and the subtraction is masked, such that
+/// time and memory access only depend on `a.len()` and `b.len()`.
+///
+/// `b` MUST NOT be zero.
+pub(crate) fn mod_slice<T: Limb>(a: &[T], b: &[T], r: &mut [T]) {
+    for d in r.iter_mut() {
+        *d = T::ZERO;
+    }
+    for ai in a.iter().rev() {
+        for j in (0..T::BITS).rev() {
+            // r < b at the start of every iteration, so 2r + 1 < 2b and the
+            // bit shifted out at the top is enough to hold the overflow.
+            let hi = shl1_slice(r, (*ai >> j) & T::ONE);
+            let gte = hi | (lt_slice(r, b) ^ T::ONE);
+            csub_assign_slice(r, b, gte);
+        }
+    }
+}
+
+/// `r = (a + b) % m` with `r.len() == m.len()`.
+///
+/// `a` and `b` MUST be smaller than `m`, such that `a + b < 2m` and a single
+/// conditional subtraction of `m` is enough.
+pub(crate) fn add_mod_slice<T: Limb>(a: &[T], b: &[T], m: &[T], r: &mut [T]) {
+    // a and b are smaller than m, so any limbs above m.len() are zero.
+    let a_len = min(a.len(), r.len());
+    let b_len = min(b.len(), r.len());
+    for d in r.iter_mut() {
+        *d = T::ZERO;
+    }
+    r[..a_len].copy_from_slice(&a[..a_len]);
+    let carry = add_assign_slice(r, &b[..b_len]);
+    let gte = carry | (lt_slice(r, m) ^ T::ONE);
+    csub_assign_slice(r, m, gte);
+}
+
+/// `r = (a * b) % m` with `r.len() == m.len()`.
+/// `scratch` is used for the full product and MUST have `a.len() + b.len()`
+/// limbs.
+///
+/// `m` MUST NOT be zero.
+pub(crate) fn mul_mod_slice<T: Limb>(a: &[T], b: &[T], m: &[T], r: &mut [T], scratch: &mut [T]) {
+    mul_slice(a, b, scratch);
+    mod_slice(scratch, m, r);
+}
+
+/// `r = table[i]` without accessing memory depending on `i`.
+/// The table is stored as consecutive entries of `r.len()` limbs.
+/// Every entry is read and masked with `equal(k, i)`.
+fn table_lookup_slice<T: Limb>(table: &[T], i: T, r: &mut [T]) {
+    for d in r.iter_mut() {
+        *d = T::ZERO;
+    }
+    for (k, entry) in table.chunks(r.len()).enumerate() {
+        let mask = mask(T::equal(&T::from_u64(k as u64), &i));
+        for (d, e) in r.iter_mut().zip(entry.iter()) {
+            *d = *d | (*e & mask);
+        }
+    }
+}
+
+/// Return the `window` bits of `a` starting at bit `pos`.
+/// Bits beyond the end of `a` are 0.
+fn get_bits_slice<T: Limb>(a: &[T], pos: usize, window: usize) -> T {
+    let mut res = T::ZERO;
+    for k in (0..window).rev() {
+        let bit_pos = pos + k;
+        // This is ok, the position is not sensitive.
+        let bit = if bit_pos < a.len() * T::BITS {
+            (a[bit_pos / T::BITS] >> (bit_pos % T::BITS)) & T::ONE
+        } else {
+            T::ZERO
+        };
+        res = (res << 1) | bit;
+    }
+    res
+}
+
+/// `r = (a ^ b) % m` with `r.len() == n`, where `m` is an `n` limb modulus.
+///
+/// This is a fixed window exponentiation. The table `a^0, ..., a^(2^window - 1)`
+/// is precomputed and the exponent is processed `window` bits at a time,
+/// starting with the most significant window. Every window takes `window`
+/// squarings and one multiplication with a table entry, even if the bits are
+/// all zero. Squarings use `sqr_slice`. The table is read with
+/// `table_lookup_slice`.
+///
+/// All reductions modulo `m` are done with `reduce(x, r)`, which MUST compute
+/// `r = x % m` for `x` of any length.
+///
+/// `scratch` MUST have `(2^window + 3) * n` limbs.
+/// `window` MUST be between 1 and 8.
+pub(crate) fn pow_mod_with_slice<T, F>(
+    a: &[T],
+    b: &[T],
+    n: usize,
+    window: usize,
+    r: &mut [T],
+    scratch: &mut [T],
+    mut reduce: F,
+) where
+    T: Limb,
+    F: FnMut(&[T], &mut [T]),
+{
+    let (table, scratch) = scratch.split_at_mut((1 << window) * n);
+    let (scratch, tmp) = scratch.split_at_mut(2 * n);
+
+    reduce(&[T::ONE], r);
+    table[..n].copy_from_slice(r);
+    reduce(a, tmp);
+    for i in 1..(1 << window) {
+        let (prev, next) = table.split_at_mut(i * n);
+        mul_slice(&prev[(i - 1) * n..], tmp, scratch);
+        reduce(scratch, &mut next[..n]);
+    }
+
+    let windows = (b.len() * T::BITS).div_ceil(window);
+    for i in (0..windows).rev() {
+        for _ in 0..window {
+            sqr_slice(r, scratch);
+            reduce(scratch, r);
+        }
+        table_lookup_slice(table, get_bits_slice(b, i * window, window), tmp);
+        mul_slice(r, tmp, scratch);
+        reduce(scratch, r);
+    }
+}
+
+/// `r = a ^ b mod 2^(BITS * r.len())`
+///
+/// This is a left-to-right square-and-multiply that branches on the bits of
+/// `b`. This is ok because `b` is public. The multiplications are constant
+/// time in `a`.
+/// `tmp` MUST have `r.len()` limbs.
+pub(crate) fn pow_slice<T: Limb>(a: &[T], b: u64, r: &mut [T], tmp: &mut [T]) {
+    for d in r.iter_mut() {
+        *d = T::ZERO;
+    }
+    if r.is_empty() {
+        return;
+    }
+    r[0] = T::ONE;
+    for i in (0..(64 - b.leading_zeros())).rev() {
+        sqr_slice(r, tmp);
+        if (b >> i) & 1 == 1 {
+            mul_slice(tmp, a, r);
+        } else {
+            r.copy_from_slice(tmp);
+        }
+    }
+}
+
+/// Panic if `a` is zero.
+/// This looks at all limbs, but the result is not considered secret.
+pub(crate) fn assert_nonzero<T: Limb>(a: &[T]) {
+    let acc = a.iter().fold(T::ZERO, |acc, d| acc | *d);
+    if acc == T::ZERO {
+        panic!("{}", UintError::DivisionByZero);
+    }
+}
+
+/// Panic if `a >= b`.
+/// The comparison is constant time, but the result is not considered secret.
+pub(crate) fn assert_reduced<T: Limb>(a: &[T], b: &[T]) {
+    if lt_slice(a, b) == T::ZERO {
+        panic!("{}", UintError::NotReduced);
+    }
+}
+
+// ===================== BARRETT ===========================
+
+/// `q = a / b` and `r = a % b` with `q.len() == a.len()` and
+/// `r.len() == b.len()`.
+///
+/// This is the same bit-serial reduction as `mod_slice`, which additionally
+/// records whether `b` was subtracted as the quotient bit.
+///
+/// `b` MUST NOT be zero.
+pub(crate) fn div_rem_slice<T: Limb>(a: &[T], b: &[T], q: &mut [T], r: &mut [T]) {
+    for d in r.iter_mut() {
+        *d = T::ZERO;
+    }
+    for (i, ai) in a.iter().enumerate().rev() {
+        let mut qi = T::ZERO;
+        for j in (0..T::BITS).rev() {
+            let hi = shl1_slice(r, (*ai >> j) & T::ONE);
+            let gte = hi | (lt_slice(r, b) ^ T::ONE);
+            csub_assign_slice(r, b, gte);
+            qi = qi | (gte << j);
+        }
+        q[i] = qi;
+    }
+}
+
+/// The number of limbs of `m` without leading zero limbs.
+/// This branches on the value of `m`, which MUST be public.
+pub(crate) fn significant_limbs<T: Limb>(m: &[T]) -> usize {
+    m.iter().rposition(|d| *d != T::ZERO).map_or(0, |i| i + 1)
+}
+
+/// Return `mu = floor(2^(2 * BITS * k) / m)` where `k` is the number of
+/// significant limbs of `m`. `mu` has `k + 2` limbs, the top one is only set
+/// if `m` is a power of `2^BITS`.
+///
+/// `m` MUST NOT be zero and is considered public.
+pub(crate) fn barrett_mu<T: Limb>(m: &[T]) -> Vec<T> {
+    let k = significant_limbs(m);
+    let mut mu = vec![T::ZERO; k + 2];
+    let mut scratch = Scratch::new(5 * k + 2);
+    barrett_mu_slice(&m[..k], &mut mu, &mut scratch);
+    mu
+}
+
+/// `mu = floor(2^(2 * BITS * k) / m)` with `k = m.len()` and
+/// `mu.len() == k + 2`.
+/// This takes time depending only on `k`.
+///
+/// The top limb of `m` MUST NOT be zero.
+/// `scratch` MUST have at least `5 * k + 2` limbs.
+pub(crate) fn barrett_mu_slice<T: Limb>(m: &[T], mu: &mut [T], scratch: &mut [T]) {
+    let k = m.len();
+    let (x, scratch) = scratch.split_at_mut(2 * k + 1);
+    let (q, scratch) = scratch.split_at_mut(2 * k + 1);
+    for d in x.iter_mut() {
+        *d = T::ZERO;
+    }
+    x[2 * k] = T::ONE;
+    div_rem_slice(x, m, q, &mut scratch[..k]);
+    mu.copy_from_slice(&q[..k + 2]);
+}
+
+/// Shift `m` to the left until its top bit is set, i.e. multiply it with the
+/// largest power of two such that it still fits into `m.len()` limbs.
+///
+/// Every one of the `BITS * m.len()` steps shifts by one bit and keeps the
+/// result if the top bit wasn't set yet, such that the number of leading
+/// zeros isn't leaked.
+/// `tmp` MUST have `m.len()` limbs and `m` MUST NOT be zero.
+pub(crate) fn normalize_slice<T: Limb>(m: &mut [T], tmp: &mut [T]) {
+    for _ in 0..m.len() * T::BITS {
+        tmp.copy_from_slice(m);
+        let top = shl1_slice(tmp, T::ZERO);
+        cassign_slice(m, tmp, top ^ T::ONE);
+    }
+}
+
+/// The number of scratch limbs `barrett_reduce_slice` needs for `mu`.
+pub(crate) fn barrett_scratch_len<T: Limb>(mu: &[T]) -> usize {
+    4 * mu.len()
+}
+
+/// `r = x % m`, using `mu` from `barrett_mu(m)`.
+/// `r` MUST have at least `k` limbs, where `k` is the number of significant
+/// limbs of `m`. Any limbs above are set to zero.
+///
+/// With `x < 2^(2 * BITS * k)`, the
+/// quotient is estimated as `((x / b^(k-1)) * mu) / b^(k+1)`, which is at
+/// most 2 smaller than the real quotient. So after subtracting the estimate
+/// times `m`, `m` is conditionally subtracted exactly twice.
+///
+/// Inputs with more than `2k` limbs are reduced with `mod_slice` instead.
+/// `scratch` MUST have `barrett_scratch_len(mu)` limbs.
+pub(crate) fn barrett_reduce_slice<T: Limb>(
+    x: &[T],
+    m: &[T],
+    mu: &[T],
+    r: &mut [T],
+    scratch: &mut [T],
+) {
+    let k = mu.len() - 2;
+    let m = &m[..k];
+    for d in r.iter_mut() {
+        *d = T::ZERO;
+    }
+    let r = &mut r[..k];
+    if x.len() > 2 * k {
+        mod_slice(x, m, r);
+        return;
+    }
+    let (q, scratch) = scratch.split_at_mut(2 * k + 3);
+    let (t, scratch) = scratch.split_at_mut(k + 1);
+    let qm = &mut scratch[..k + 1];
+
+    // q = (x / b^(k-1)) * mu, the estimate is q / b^(k+1).
+    let q1 = if x.len() > k - 1 { &x[k - 1..] } else { &[] };
+    mul_slice(q1, mu, q);
+
+    // t = (x - estimate * m) mod b^(k+1)
+    for d in t.iter_mut() {
+        *d = T::ZERO;
+    }
+    let len = min(x.len(), k + 1);
+    t[..len].copy_from_slice(&x[..len]);
+    mul_slice(&q[k + 1..], m, qm);
+    sub_assign_slice(t, qm);
+
+    for _ in 0..2 {
+        let gte = gte_slice(t, m);
+        csub_assign_slice(t, m, gte);
+    }
+    r.copy_from_slice(&t[..k]);
+}
+
+/// The number of scratch limbs `pow_mod_barrett_slice` needs for an `n` limb
+/// modulus.
+pub(crate) fn pow_mod_barrett_scratch_len(n: usize, window: usize) -> usize {
+    ((1 << window) + 10) * n + 10
+}
+
+/// `r = (a ^ b) % m` with `r.len() == m.len()`.
+///
+/// This is `pow_mod_with_slice` with Barrett reduction for all products.
+/// Barrett reduction needs a modulus without leading zero limbs. To not leak
+/// the number of leading zeros of `m`, the exponentiation is done modulo
+/// `m * 2^s` instead, where `s` is such that the top bit is set (see
+/// `normalize_slice`). The result is then reduced modulo `m` once with
+/// `mod_slice`, which is fine because `m` divides `m * 2^s`.
+/// This takes time depending only on the lengths of `a`, `b` and `m`.
+///
+/// `scratch` MUST have `pow_mod_barrett_scratch_len(m.len(), window)` limbs.
+/// `m` MUST NOT be zero and `window` MUST be between 1 and 8.
+pub(crate) fn pow_mod_barrett_slice<T: Limb>(
+    a: &[T],
+    b: &[T],
+    m: &[T],
+    window: usize,
+    r: &mut [T],
+    scratch: &mut [T],
+) {
+    let n = m.len();
+    let (norm, scratch) = scratch.split_at_mut(n);
+    let (mu, scratch) = scratch.split_at_mut(n + 2);
+    norm.copy_from_slice(m);
+    normalize_slice(norm, &mut scratch[..n]);
+    barrett_mu_slice(norm, mu, scratch);
+
+    let (reduce_scratch, scratch) = scratch.split_at_mut(4 * (n + 2));
+    let (rn, scratch) = scratch.split_at_mut(n);
+    pow_mod_with_slice(a, b, n, window, rn, scratch, |x, r| {
+        barrett_reduce_slice(x, norm, mu, r, reduce_scratch)
+    });
+    mod_slice(rn, m, r);
+}
+
+// ===================== MONTGOMERY ===========================
+
+/// Return `(a * b + c + d lower half, a * b + c + d higher half)`.
+/// Built from `mul_with_carry` and `add_with_carry`. This never overflows.
+fn mac<T: Limb>(a: &T, b: &T, c: &T, d: &T) -> (T, T) {
+    let (lower, higher) = T::mul_with_carry(a, b);
+    let (lower, c1) = T::add_with_carry(&lower, c);
+    let (lower, c2) = T::add_with_carry(&lower, d);
+    let higher = T::add_with_carry(&higher, &c1).0;
+    (lower, T::add_with_carry(&higher, &c2).0)
+}
+
+/// Return `-m^-1 mod 2^BITS` for an odd limb `m`.
+///
+/// Every Newton step `x = x * (2 - m * x)` doubles the number of correct
+/// bits. `m` is its own inverse modulo 8, so we start with 3 correct bits.
+pub(crate) fn mont_inv_limb<T: Limb>(m: T) -> T {
+    let mut x = m;
+    let mut bits = 3;
+    while bits < T::BITS {
+        let mx = T::mul_with_carry(&m, &x).0;
+        let two_minus_mx = T::sub_with_borrow(&T::from_u64(2), &mx).0;
+        x = T::mul_with_carry(&x, &two_minus_mx).0;
+        bits *= 2;
+    }
+    T::sub_with_borrow(&T::ZERO, &x).0
+}
+
+/// `r = a * b * R^-1 mod m` with `R = 2^(BITS * m.len())`.
+///
+/// This is the coarsely integrated operand scanning (CIOS) Montgomery
+/// multiplication. Every limb of `b` is multiplied into the accumulator `t`,
+/// followed by one reduction step that adds a multiple of `m` such that the
+/// lowest limb becomes zero and shifts it out.
+/// The result is smaller than `2m` and the final subtraction of `m` is always
+/// computed and selected with `cswap`.
+///
+/// `a`, `b` and `r` MUST have `m.len()` limbs and `a` and `b` MUST be smaller
+/// than `m`. `m_inv` is `-m^-1 mod 2^BITS` and `t` MUST have `m.len() + 2`
+/// limbs.
+pub(crate) fn mont_mul_slice<T: Limb>(
+    a: &[T],
+    b: &[T],
+    m: &[T],
+    m_inv: T,
+    r: &mut [T],
+    t: &mut [T],
+) {
+    let n = m.len();
+    for d in t.iter_mut() {
+        *d = T::ZERO;
+    }
+    for bi in b.iter() {
+        // t += a * b[i]
+        let mut carry = T::ZERO;
+        for j in 0..n {
+            let (lower, higher) = mac(&a[j], bi, &t[j], &carry);
+            t[j] = lower;
+            carry = higher;
+        }
+        let (d, c) = T::add_with_carry(&t[n], &carry);
+        t[n] = d;
+        t[n + 1] = c;
+
+        // t = (t + u * m) / 2^BITS with u such that the lowest limb is 0.
+        let u = T::mul_with_carry(&t[0], &m_inv).0;
+        let (_, mut carry) = mac(&u, &m[0], &t[0], &T::ZERO);
+        for j in 1..n {
+            let (lower, higher) = mac(&u, &m[j], &t[j], &carry);
+            t[j - 1] = lower;
+            carry = higher;
+        }
+        let (d, c) = T::add_with_carry(&t[n], &carry);
+        t[n - 1] = d;
+        t[n] = T::add_with_carry(&t[n + 1], &c).0;
+    }
+
+    // t < 2m, subtract m if t[n] is set or t[..n] >= m.
+    r.copy_from_slice(&t[..n]);
+    let borrow = sub_assign_slice(&mut t[..n], m);
+    let c = t[n] | (borrow ^ T::ONE);
+    cswap_slice(r, &mut t[..n], c);
+}
+
+/// `r = a * a * R^-1 mod m`.
+///
+/// The square is computed with `sqr_slice` first and then reduced one limb
+/// at a time like in `mont_mul_slice` (separated operand scanning).
+/// The final subtraction of `m` is always computed and selected with `cswap`.
+///
+/// `a` and `r` MUST have `m.len()` limbs and `a` MUST be smaller than `m`.
+/// `m_inv` is `-m^-1 mod 2^BITS` and `t` MUST have `2 * m.len() + 1` limbs.
+pub(crate) fn mont_sqr_slice<T: Limb>(a: &[T], m: &[T], m_inv: T, r: &mut [T], t: &mut [T]) {
+    let n = m.len();
+    sqr_slice(a, &mut t[..2 * n]);
+
+    // t = (t + u * m) / 2^BITS with u such that the lowest limb is 0, n times.
+    let mut extra = T::ZERO;
+    for i in 0..n {
+        let u = T::mul_with_carry(&t[i], &m_inv).0;
+        let mut carry = T::ZERO;
+        for j in 0..n {
+            let (lower, higher) = mac(&u, &m[j], &t[i + j], &carry);
+            t[i + j] = lower;
+            carry = higher;
+        }
+        let (d, c1) = T::add_with_carry(&t[i + n], &carry);
+        let (d, c2) = T::add_with_carry(&d, &extra);
+        t[i + n] = d;
+        extra = c1 | c2;
+    }
+
+    // t[n..] < 2m, subtract m if the extra bit is set or t[n..2n] >= m.
+    let (_, t) = t.split_at_mut(n);
+    r.copy_from_slice(&t[..n]);
+    let borrow = sub_assign_slice(&mut t[..n], m);
+    let c = extra | (borrow ^ T::ONE);
+    cswap_slice(r, &mut t[..n], c);
+}
+
+/// `r = a ^ b * R^-(b-1) mod m`, i.e. the Montgomery form of `x ^ b` if `a`
+/// is the Montgomery form of `x`.
+///
+/// This is the same fixed window exponentiation as `pow_mod_with_slice`, using
+/// Montgomery multiplications. `one` is `R mod m`.
+///
+/// `a`, `one` and `r` MUST have `m.len()` limbs and `a` MUST be smaller than
+/// `m`. `scratch` MUST have `(2^window + 4) * m.len() + 1` limbs.
+/// `window` MUST be between 1 and 8.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn mont_pow_slice<T: Limb>(
+    a: &[T],
+    b: &[T],
+    m: &[T],
+    m_inv: T,
+    one: &[T],
+    window: usize,
+    r: &mut [T],
+    scratch: &mut [T],
+) {
+    let n = m.len();
+    let (table, scratch) = scratch.split_at_mut((1 << window) * n);
+    let (tmp, t) = scratch.split_at_mut(n);
+    let (t, entry) = t.split_at_mut(2 * n + 1);
+
+    table[..n].copy_from_slice(one);
+    for i in 1..(1 << window) {
+        let (prev, next) = table.split_at_mut(i * n);
+        mont_mul_slice(
+            &prev[(i - 1) * n..],
+            a,
+            m,
+            m_inv,
+            &mut next[..n],
+            &mut t[..n + 2],
+        );
+    }
+
+    r.copy_from_slice(one);
+    let windows = (b.len() * T::BITS).div_ceil(window);
+    for i in (0..windows).rev() {
+        for _ in 0..window {
+            mont_sqr_slice(r, m, m_inv, tmp, t);
+            r.copy_from_slice(tmp);
+        }
+        table_lookup_slice(table, get_bits_slice(b, i * window, window), entry);
+        mont_mul_slice(r, entry, m, m_inv, tmp, &mut t[..n + 2]);
+        r.copy_from_slice(tmp);
+    }
+}
+
+// ===================== FAST MULTIPLICATION ===========================
+
+/// `a = a / 2` for an even `a` in two's complement, i.e. an arithmetic shift
+/// to the right by one bit.
+fn sar1_slice<T: Limb>(a: &mut [T]) {
+    let mut carry = match a.last() {
+        Some(d) => *d >> (T::BITS - 1),
+        None => return,
+    };
+    for d in a.iter_mut().rev() {
+        let next = *d & T::ONE;
+        *d = (*d >> 1) | (carry << (T::BITS - 1));
+        carry = next;
+    }
+}
+
+/// `a = -a` in two's complement if `c == 1`, `a` is left untouched if
+/// `c == 0`.
+fn cneg_slice<T: Limb>(a: &mut [T], c: T) {
+    let m = mask(c);
+    let mut carry = c;
+    for d in a.iter_mut() {
+        let (r, c1) = T::add_with_carry(&(*d ^ m), &carry);
+        *d = r;
+        carry = c1;
+    }
+}
+
+/// `res = a * b` for two's complement `a` and `b` of the same length and
+/// `res.len() == 2 * a.len()`.
+///
+/// The magnitudes are multiplied with `mul_into` and the product is negated
+/// if the signs differ. `a` and `b` are negated in place and restored
+/// afterwards.
+fn mul_signed_into<T: Limb>(
+    a: &mut [T],
+    b: &mut [T],
+    res: &mut [T],
+    karatsuba: usize,
+    toom3: usize,
+) {
+    let sa = a[a.len() - 1] >> (T::BITS - 1);
+    let sb = b[b.len() - 1] >> (T::BITS - 1);
+    cneg_slice(a, sa);
+    cneg_slice(b, sb);
+    mul_into(a, b, res, karatsuba, toom3);
+    cneg_slice(res, sa ^ sb);
+    cneg_slice(a, sa);
+    cneg_slice(b, sb);
+}
+
+/// `a = a / d` for an odd limb `d` that divides `a` (in two's complement).
+///
+/// This is an exact (Hensel) division from the least significant limb up.
+/// Every limb is multiplied with `d^-1 mod 2^BITS` and the part of
+/// `q_i * d` above the limb is subtracted from the next limb.
+/// `d` MUST be odd and the result is only correct if the division is exact.
+fn divexact_slice<T: Limb>(a: &mut [T], d: T) {
+    let inv = T::sub_with_borrow(&T::ZERO, &mont_inv_limb(d)).0;
+    let mut carry = T::ZERO;
+    for ai in a.iter_mut() {
+        let (s, borrow) = T::sub_with_borrow(ai, &carry);
+        let q = T::mul_with_carry(&s, &inv).0;
+        *ai = q;
+        // q * d = s + (borrow + carry) * 2^BITS
+        let higher = T::mul_with_carry(&q, &d).1;
+        carry = T::add_with_carry(&higher, &borrow).0;
+    }
+}
+
+/// `res = a * b` with `res.len() == a.len() + b.len()`.
+///
+/// This picks the multiplication algorithm from the lengths alone:
+/// * Operands with less than `karatsuba` limbs use `mul_slice`.
+/// * If the shorter operand is at most half as long as the longer one, the
+///   longer one is multiplied in chunks of the shorter length.
+/// * Balanced operands with at least `toom3` limbs use `toom3_into`.
+/// * Everything else uses `karatsuba_into`.
+///
+/// Temporary values are allocated as `Scratch` buffers.
+/// `karatsuba` MUST be at least 4, otherwise the Karatsuba sums are as long
+/// as the operands.
+fn mul_into<T: Limb>(a: &[T], b: &[T], res: &mut [T], karatsuba: usize, toom3: usize) {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    if b.len() < karatsuba {
+        mul_slice(a, b, res);
+        return;
+    }
+
+    let n = a.len();
+    if b.len() <= n / 2 {
+        for d in res.iter_mut() {
+            *d = T::ZERO;
+        }
+        for (i, chunk) in a.chunks(b.len()).enumerate() {
+            let mut product = Scratch::new(chunk.len() + b.len());
+            mul_into(chunk, b, &mut product, karatsuba, toom3);
+            add_assign_slice(&mut res[i * b.len()..], &product);
+        }
+        return;
+    }
+
+    if b.len() >= toom3 && b.len() > 2 * n.div_ceil(3) {
+        toom3_into(a, b, res, karatsuba, toom3);
+    } else {
+        karatsuba_into(a, b, res, karatsuba, toom3);
+    }
+}
+
+/// `res = a * b` with `res.len() == a.len() + b.len()` and
+/// `n / 2 < b.len() <= a.len() == n`.
+///
+/// Karatsuba multiplication. The operands are split at `n / 2` and `a * b`
+/// is computed from the three products `a0 * b0`, `a1 * b1` and
+/// `(a0 + a1) * (b0 + b1)`. The sums always get a full carry limb, so the
+/// shape of the recursion only depends on the lengths.
+/// The products are computed with `mul_into`.
+fn karatsuba_into<T: Limb>(a: &[T], b: &[T], res: &mut [T], karatsuba: usize, toom3: usize) {
+    // b.len() > n / 2, so both high halves are non-empty.
+    let h = a.len() / 2;
+    let (a0, a1) = a.split_at(h);
+    let (b0, b1) = b.split_at(h);
+
+    // res = a0 * b0 + a1 * b1 * 2^(2 * h * BITS)
+    {
+        let (lo, hi) = res.split_at_mut(2 * h);
+        mul_into(a0, b0, lo, karatsuba, toom3);
+        mul_into(a1, b1, hi, karatsuba, toom3);
+    }
+
+    // mid = (a0 + a1) * (b0 + b1) - a0 * b0 - a1 * b1
+    let mut sa = Scratch::new(max(a0.len(), a1.len()) + 1);
+    sa[..a1.len()].copy_from_slice(a1);
+    add_assign_slice(&mut sa, a0);
+    let mut sb = Scratch::new(max(b0.len(), b1.len()) + 1);
+    sb[..b0.len()].copy_from_slice(b0);
+    add_assign_slice(&mut sb, b1);
+    let mut mid = Scratch::new(sa.len() + sb.len());
+    mul_into(&sa, &sb, &mut mid, karatsuba, toom3);
+    sub_assign_slice(&mut mid, &res[..2 * h]);
+    sub_assign_slice(&mut mid, &res[2 * h..]);
+
+    // The top limbs of mid are zero if they don't fit.
+    let len = min(mid.len(), res.len() - h);
+    add_assign_slice(&mut res[h..], &mid[..len]);
+}
+
+/// Evaluate `a0 + a1 * x + a2 * x^2` at `x = 1, -1, -2` into `p1`, `pm1`
+/// and `pm2`, which all have the same length and hold two's complement
+/// values.
+fn toom3_evaluate<T: Limb>(a: &[T], k: usize, p1: &mut [T], pm1: &mut [T], pm2: &mut [T]) {
+    let (a0, a1) = a.split_at(k);
+    let (a1, a2) = a1.split_at(k);
+
+    // pm1 = a0 + a2, p1 = pm1 + a1, pm1 = pm1 - a1
+    for d in pm1.iter_mut() {
+        *d = T::ZERO;
+    }
+    pm1[..k].copy_from_slice(a0);
+    add_assign_slice(pm1, a2);
+    p1.copy_from_slice(pm1);
+    add_assign_slice(p1, a1);
+    sub_assign_slice(pm1, a1);
+
+    // pm2 = (pm1 + a2) * 2 - a0
+    pm2.copy_from_slice(pm1);
+    add_assign_slice(pm2, a2);
+    shl1_slice(pm2, T::ZERO);
+    sub_assign_slice(pm2, a0);
+}
+
+/// `res = a * b` with `res.len() == a.len() + b.len()`, `k = ceil(n / 3)`
+/// and `2k < b.len() <= a.len() == n`.
+///
+/// Toom-Cook 3-way multiplication. The operands are split into three parts
+/// of `k` limbs, which are the coefficients of polynomials of degree 2 in
+/// `x = 2^(k * BITS)`. These are evaluated at `0, 1, -1, -2` and infinity,
+/// multiplied point-wise with `mul_into` and the product is interpolated
+/// with Bodrato's sequence.
+///
+/// Negative values are held in two's complement with a fixed number of
+/// limbs, which is enough for all intermediate values. Products of signed
+/// values are computed with `mul_signed_into`. The interpolation
+/// only needs exact divisions by 2 and 3, which are computed with
+/// `sar1_slice` and `divexact_slice`. Everything else only depends on the
+/// lengths.
+fn toom3_into<T: Limb>(a: &[T], b: &[T], res: &mut [T], karatsuba: usize, toom3: usize) {
+    let k = a.len().div_ceil(3);
+    // |p(-2)| < 7 * 2^(k * BITS), which fits into k + 1 signed limbs.
+    let e = k + 1;
+    let w = 2 * e;
+
+    let mut p = Scratch::new(3 * e);
+    let mut q = Scratch::new(3 * e);
+    {
+        let (p1, pm) = p.split_at_mut(e);
+        let (pm1, pm2) = pm.split_at_mut(e);
+        toom3_evaluate(a, k, p1, pm1, pm2);
+        let (q1, qm) = q.split_at_mut(e);
+        let (qm1, qm2) = qm.split_at_mut(e);
+        toom3_evaluate(b, k, q1, qm1, qm2);
+    }
+
+    // r0 = a0 * b0 and r4 = a2 * b2 go straight into the result.
+    for d in res.iter_mut() {
+        *d = T::ZERO;
+    }
+    {
+        let (r0, rest) = res.split_at_mut(2 * k);
+        mul_into(&a[..k], &b[..k], r0, karatsuba, toom3);
+        mul_into(
+            &a[2 * k..],
+            &b[2 * k..],
+            &mut rest[2 * k..],
+            karatsuba,
+            toom3,
+        );
+    }
+
+    // The products are held in two's complement with w limbs again.
+    let mut r = Scratch::new(3 * w);
+    {
+        let (r1, rm) = r.split_at_mut(w);
+        let (rm1, rm2) = rm.split_at_mut(w);
+        let (p1, pm) = p.split_at_mut(e);
+        let (pm1, pm2) = pm.split_at_mut(e);
+        let (q1, qm) = q.split_at_mut(e);
+        let (qm1, qm2) = qm.split_at_mut(e);
+        mul_into(p1, q1, r1, karatsuba, toom3);
+        mul_signed_into(pm1, qm1, rm1, karatsuba, toom3);
+        mul_signed_into(pm2, qm2, rm2, karatsuba, toom3);
+    }
+    toom3_interpolate(&mut r, k, res);
+}
+
+/// Interpolate the Toom-3 product from `r(0)` in `res[..2k]`, `r(inf)` in
+/// `res[4k..]` and `r(1)`, `r(-1)`, `r(-2)` in `r`, and add the remaining
+/// coefficients to `res`.
+///
+/// The values in `r` are two's complement with `w = r.len() / 3` limbs and
+/// are overwritten.
+fn toom3_interpolate<T: Limb>(r: &mut [T], k: usize, res: &mut [T]) {
+    let w = r.len() / 3;
+    let (r0, r4) = (&res[..2 * k], &res[4 * k..]);
+    let mut r2 = Scratch::new(w);
+    let mut r3 = Scratch::new(w);
+    {
+        let (r1, rm) = r.split_at_mut(w);
+        let (rm1, rm2) = rm.split_at_mut(w);
+
+        // r3 = (r(-2) - r(1)) / 3
+        r3.copy_from_slice(rm2);
+        sub_assign_slice(&mut r3, r1);
+        divexact_slice(&mut r3, T::from_u64(3));
+        // r1 = (r(1) - r(-1)) / 2
+        sub_assign_slice(r1, rm1);
+        sar1_slice(r1);
+        // r2 = r(-1) - r(0)
+        r2.copy_from_slice(rm1);
+        sub_assign_slice(&mut r2, r0);
+        // r3 = (r2 - r3) / 2 + 2 * r(inf)
+        rm2.copy_from_slice(&r2);
+        sub_assign_slice(rm2, &r3);
+        sar1_slice(rm2);
+        add_assign_slice(rm2, r4);
+        add_assign_slice(rm2, r4);
+        r3.copy_from_slice(rm2);
+        // r2 = r2 + r1 - r(inf)
+        add_assign_slice(&mut r2, r1);
+        sub_assign_slice(&mut r2, r4);
+        // r1 = r1 - r3
+        sub_assign_slice(r1, &r3);
+    }
+
+    // res += r1 * x + r2 * x^2 + r3 * x^3
+    // The coefficients are not negative and their top limbs are zero if
+    // they don't fit.
+    for (i, ri) in [&r[..w], &r2[..], &r3[..]].iter().enumerate() {
+        let offset = (i + 1) * k;
+        let len = min(w, res.len() - offset);
+        add_assign_slice(&mut res[offset..], &ri[..len]);
+    }
+}
+
+/// `res = a * a` with `res.len() == 2 * a.len()`.
+///
+/// The same as `mul_into(a, a, ..)`, but all products in the recursion are
+/// squares and use `sqr_slice` once they are shorter than `karatsuba` limbs.
+fn sqr_into<T: Limb>(a: &[T], res: &mut [T], karatsuba: usize, toom3: usize) {
+    let n = a.len();
+    if n < karatsuba {
+        sqr_slice(a, res);
+    } else if n >= toom3 && n > 2 * n.div_ceil(3) {
+        toom3_sqr_into(a, res, karatsuba, toom3);
+    } else {
+        karatsuba_sqr_into(a, res, karatsuba, toom3);
+    }
+}
+
+/// `res = a * a` with `res.len() == 2 * a.len()`.
+/// Karatsuba squaring, see `karatsuba_into`.
+fn karatsuba_sqr_into<T: Limb>(a: &[T], res: &mut [T], karatsuba: usize, toom3: usize) {
+    let h = a.len() / 2;
+    let (a0, a1) = a.split_at(h);
+
+    // res = a0^2 + a1^2 * 2^(2 * h * BITS)
+    {
+        let (lo, hi) = res.split_at_mut(2 * h);
+        sqr_into(a0, lo, karatsuba, toom3);
+        sqr_into(a1, hi, karatsuba, toom3);
+    }
+
+    // mid = (a0 + a1)^2 - a0^2 - a1^2
+    let mut sa = Scratch::new(a1.len() + 1);
+    sa[..a1.len()].copy_from_slice(a1);
+    add_assign_slice(&mut sa, a0);
+    let mut mid = Scratch::new(2 * sa.len());
+    sqr_into(&sa, &mut mid, karatsuba, toom3);
+    sub_assign_slice(&mut mid, &res[..2 * h]);
+    sub_assign_slice(&mut mid, &res[2 * h..]);
+
+    // The top limbs of mid are zero if they don't fit.
+    let len = min(mid.len(), res.len() - h);
+    add_assign_slice(&mut res[h..], &mid[..len]);
+}
+
+/// `res = a * a` with `res.len() == 2 * a.len()` and `2k < a.len()` for
+/// `k = ceil(a.len() / 3)`.
+/// Toom-3 squaring, see `toom3_into`. The squares of the evaluations don't
+/// depend on their signs, so only the magnitudes are squared.
+fn toom3_sqr_into<T: Limb>(a: &[T], res: &mut [T], karatsuba: usize, toom3: usize) {
+    let k = a.len().div_ceil(3);
+    let e = k + 1;
+    let w = 2 * e;
+
+    let mut p = Scratch::new(3 * e);
+    {
+        let (p1, pm) = p.split_at_mut(e);
+        let (pm1, pm2) = pm.split_at_mut(e);
+        toom3_evaluate(a, k, p1, pm1, pm2);
+    }
+
+    // r0 = a0^2 and r4 = a2^2 go straight into the result.
+    {
+        let (r0, rest) = res.split_at_mut(2 * k);
+        sqr_into(&a[..k], r0, karatsuba, toom3);
+        sqr_into(&a[2 * k..], &mut rest[2 * k..], karatsuba, toom3);
+        for d in rest[..2 * k].iter_mut() {
+            *d = T::ZERO;
+        }
+    }
+
+    let mut r = Scratch::new(3 * w);
+    for (pi, ri) in p.chunks_mut(e).zip(r.chunks_mut(w)) {
+        let sign = pi[e - 1] >> (T::BITS - 1);
+        cneg_slice(pi, sign);
+        sqr_into(pi, ri, karatsuba, toom3);
+    }
+    toom3_interpolate(&mut r, k, res);
+}
+
+// ===================== VECTORS ===========================
+
+/// A very generic way of summing up two vectors.
+pub(crate) fn add_generic<T: Limb>(a: &[T], b: &[T]) -> Vec<T> {
+    let (longer, shorter) = if a.len() > b.len() { (a, b) } else { (b, a) };
+    // Reserve the carry limb up front, so `push` doesn't reallocate and leave
+    // a copy behind.
+    let mut res = Vec::with_capacity(longer.len() + 1);
+    res.extend_from_slice(longer);
+    let carry = add_assign_slice(&mut res, shorter);
+    res.push(carry);
+    res
+}
+
+/// Subtract two vectors.
+/// The result has as many limbs as the longer input and wraps around.
+/// Returns the borrow, i.e. 1 if `a < b` and 0 otherwise.
+pub(crate) fn sub_generic<T: Limb>(a: &[T], b: &[T]) -> (Vec<T>, T) {
+    let len = max(a.len(), b.len());
+    let mut res = Vec::with_capacity(len);
+    res.extend_from_slice(a);
+    res.resize(len, T::ZERO);
+    let borrow = sub_assign_slice(&mut res, b);
+    (res, borrow)
+}
+
+/// Multiply two vectors with schoolbook, Karatsuba or Toom-3
+/// multiplication, depending on the lengths. See `mul_into`.
+///
+/// The result has `a.len() + b.len()` limbs.
+/// `karatsuba` MUST be at least 4.
+pub(crate) fn mul_fast_generic<T: Limb>(
+    a: &[T],
+    b: &[T],
+    karatsuba: usize,
+    toom3: usize,
+) -> Vec<T> {
+    let mut res = vec![T::ZERO; a.len() + b.len()];
+    mul_into(a, b, &mut res, karatsuba, toom3);
+    res
+}
+
+/// Square a vector, see `sqr_into`.
+///
+/// The result has `2 * a.len()` limbs.
+/// `karatsuba` MUST be at least 4.
+pub(crate) fn sqr_fast_generic<T: Limb>(a: &[T], karatsuba: usize, toom3: usize) -> Vec<T> {
+    let mut res = vec![T::ZERO; 2 * a.len()];
+    sqr_into(a, &mut res, karatsuba, toom3);
+    res
+}
+
+/// Return `a % b`
+///
+/// The result has `b.len()` limbs.
+/// `b` MUST NOT be zero.
+pub(crate) fn mod_generic<T: Limb>(a: &[T], b: &[T]) -> Vec<T> {
+    let mut r = vec![T::ZERO; b.len()];
+    mod_slice(a, b, &mut r);
+    r
+}
+
+/// Return `(a + b) % c`
+///
+/// `a` and `b` MUST be smaller than `c`.
+/// The result has `c.len()` limbs.
+pub(crate) fn add_mod_generic<T: Limb>(a: &[T], b: &[T], c: &[T]) -> Vec<T> {
+    let mut r = vec![T::ZERO; c.len()];
+    add_mod_slice(a, b, c, &mut r);
+    r
+}
+
+/// Return `(a * b) % c`
+///
+/// The full product is computed first and then reduced.
+/// The inputs don't have to be reduced.
+///
+/// The result has `c.len()` limbs.
+/// `c` MUST NOT be zero.
+pub(crate) fn mul_mod_generic<T: Limb>(a: &[T], b: &[T], c: &[T]) -> Vec<T> {
+    let mut r = vec![T::ZERO; c.len()];
+    let mut scratch = Scratch::new(a.len() + b.len());
+    mul_mod_slice(a, b, c, &mut r, &mut scratch);
+    r
+}
+
+/// Return `(a ^ b) % c`
+///
+/// See `pow_mod_barrett_slice` for details. The reductions use
+/// `barrett_reduce_slice` with `mu` computed once.
+///
+/// The result has `c.len()` limbs.
+/// `c` MUST NOT be zero and `window` MUST be between 1 and 8.
+pub(crate) fn pow_mod_generic<T: Limb>(a: &[T], b: &[T], c: &[T], window: usize) -> Vec<T> {
+    let mut r = vec![T::ZERO; c.len()];
+    let mut scratch = Scratch::new(pow_mod_barrett_scratch_len(c.len(), window));
+    pow_mod_barrett_slice(a, b, c, window, &mut r, &mut scratch);
+    r
+}
+
+/// Return `(a * b) % c` with `mu` from `barrett_mu(c)`.
+///
+/// The result has `c.len()` limbs.
+pub(crate) fn mul_mod_barrett_generic<T: Limb>(a: &[T], b: &[T], c: &[T], mu: &[T]) -> Vec<T> {
+    let mut r = vec![T::ZERO; c.len()];
+    let mut product = Scratch::new(a.len() + b.len());
+    let mut scratch = Scratch::new(barrett_scratch_len(mu));
+    mul_slice(a, b, &mut product);
+    barrett_reduce_slice(&product, c, mu, &mut r, &mut scratch);
+    r
+}
+
+/// Return `(a ^ b) % c` with `mu` from `barrett_mu(c)`.
+///
+/// The result has `c.len()` limbs.
+/// `window` MUST be between 1 and 8.
+pub(crate) fn pow_mod_barrett_generic<T: Limb>(
+    a: &[T],
+    b: &[T],
+    c: &[T],
+    mu: &[T],
+    window: usize,
+) -> Vec<T> {
+    // Leading zero limbs of the public modulus are skipped, such that the
+    // products stay within 2k limbs.
+    let k = mu.len() - 2;
+    let mut r = vec![T::ZERO; c.len()];
+    let mut scratch = Scratch::new(((1 << window) + 3) * k);
+    let mut reduce_scratch = Scratch::new(barrett_scratch_len(mu));
+    pow_mod_with_slice(a, b, k, window, &mut r[..k], &mut scratch, |x, r| {
+        barrett_reduce_slice(x, &c[..k], mu, r, &mut reduce_scratch)
+    });
+    r
+}
+
+/// Return `a ^ b`
+///
+/// This is a left-to-right square-and-multiply that branches on the bits of
+/// `b`. This is ok because `b` is public. The multiplications are constant
+/// time in `a`.
+///
+/// The multiplications use `mul_fast_generic` and `sqr_fast_generic` with
+/// the given thresholds.
+/// The result has `b * a.len()` limbs, or a single limb if `b` is 0.
+pub(crate) fn pow_generic<T: Limb>(a: &[T], b: u64, karatsuba: usize, toom3: usize) -> Vec<T> {
+    if b == 0 {
+        return vec![T::ONE];
+    }
+
+    // Start with the most significant bit, which is always set.
+    // Intermediate powers are wiped when they are replaced.
+    let mut r = Scratch::from_slice(a);
+    for i in (0..(63 - b.leading_zeros())).rev() {
+        r = Scratch(sqr_fast_generic(&r, karatsuba, toom3));
+        if (b >> i) & 1 == 1 {
+            r = Scratch(mul_fast_generic(&r, a, karatsuba, toom3));
+        }
+    }
+    r.into_vec()
+}
@@ -0,0 +1,86 @@
+//!
+//! Barrett reduction modulo a fixed modulus `m`.
+//!
+//! `BarrettReducer` precomputes `mu = floor(b^2k / m)` for a modulus with `k`
+//! significant limbs of `b = 2^BITS`. Afterwards every reduction of a double
+//! width value only takes two multiplications and two conditional
+//! subtractions instead of a bit-serial division. Unlike `montgomery` this
+//! works for even moduli as well.
+//!
+//! The modulus is considered public, all other values are processed in
+//! constant time.
+//!
+
+use algorithms::*;
+use base::*;
+use uint::DEFAULT_WINDOW_SIZE;
+use util::*;
+
+/// Precomputed values for reductions modulo `m`.
+#[derive(Debug, Clone)]
+pub struct BarrettReducer<T: Limb> {
+    /// The modulus `m`.
+    modulus: Uint<T>,
+    /// `floor(b^2k / m)` with `k + 2` limbs.
+    mu: Vec<T>,
+}
+
+impl<T: Limb> BarrettReducer<T> {
+    /// Precompute `floor(b^2k / m)` for `modulus`.
+    ///
+    /// Fails with `UintError::DivisionByZero` if `modulus` is zero.
+    pub fn new(modulus: &Uint<T>) -> Result<Self, UintError> {
+        // This is ok, the modulus is public.
+        if significant_limbs(&modulus.digits) == 0 {
+            return Err(UintError::DivisionByZero);
+        }
+        Ok(Self {
+            modulus: modulus.clone(),
+            mu: barrett_mu(&modulus.digits),
+        })
+    }
+
+    /// The modulus `m`.
+    pub fn modulus(&self) -> &Uint<T> {
+        &self.modulus
+    }
+
+    /// Compute `x % m`.
+    ///
+    /// `x` can have up to `2k` limbs, e.g. the product of two reduced values.
+    /// Longer inputs are reduced with `UintTrait::mod_`.
+    /// The result has as many limbs as the modulus.
+    pub fn reduce(&self, x: &Uint<T>) -> Uint<T> {
+        let mut r = vec![T::ZERO; self.modulus.digits.len()];
+        let mut scratch = Scratch::new(barrett_scratch_len(&self.mu));
+        barrett_reduce_slice(
+            &x.digits,
+            &self.modulus.digits,
+            &self.mu,
+            &mut r,
+            &mut scratch,
+        );
+        Uint { digits: r }
+    }
+
+    /// Compute `a * b % m`. See `UintTrait::mul_mod_`.
+    /// The result has as many limbs as the modulus.
+    pub fn mul_mod(&self, a: &Uint<T>, b: &Uint<T>) -> Uint<T> {
+        let res = mul_mod_barrett_generic(&a.digits, &b.digits, &self.modulus.digits, &self.mu);
+        Uint { digits: res }
+    }
+
+    /// Compute `base ^ exp % m`. See `UintTrait::pow_mod_`.
+    /// The exponent is considered secret.
+    /// The result has as many limbs as the modulus.
+    pub fn pow_mod(&self, base: &Uint<T>, exp: &Uint<T>) -> Uint<T> {
+        let res = pow_mod_barrett_generic(
+            &base.digits,
+            &exp.digits,
+            &self.modulus.digits,
+            &self.mu,
+            DEFAULT_WINDOW_SIZE,
+        );
+        Uint { digits: res }
+    }
+}
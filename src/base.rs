@@ -1,39 +1,205 @@
+use std::error::Error;
+use std::fmt;
 use std::str::FromStr;
 
+use util::Limb;
+
 /// Uint errors
-/// * StringParsingError when a string can't be parsed into a Uint.
-#[derive(Debug)]
+/// * MissingPrefix when a hex string doesn't start with `0x`.
+/// * EmptyString when a string has no digits.
+/// * InvalidDigit when a string has a character that is not a digit in the
+///   given radix. This holds the byte offset of the first such character.
+/// * Overflow when a value doesn't fit into the requested width.
+/// * DivisionByZero when reducing modulo zero.
+/// * NotReduced when an operand is not smaller than the modulus.
+/// * EvenModulus when an odd modulus is required.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UintError {
-    StringParsingError,
+    MissingPrefix,
+    EmptyString,
+    InvalidDigit(usize),
+    Overflow,
+    DivisionByZero,
+    NotReduced,
+    EvenModulus,
+}
+
+impl fmt::Display for UintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            UintError::MissingPrefix => write!(f, "hex string must start with `0x`"),
+            UintError::EmptyString => write!(f, "cannot parse integer from empty string"),
+            UintError::InvalidDigit(offset) => write!(f, "invalid digit at byte {}", offset),
+            UintError::Overflow => write!(f, "number too large to fit in target type"),
+            UintError::DivisionByZero => {
+                write!(
+                    f,
+                    "attempt to calculate the remainder with a divisor of zero"
+                )
+            }
+            UintError::NotReduced => write!(f, "operand is not reduced modulo the modulus"),
+            UintError::EvenModulus => write!(f, "modulus must be odd"),
+        }
+    }
 }
 
-// TODO: restrict T?
+impl Error for UintError {}
+
+/// Fail with `UintError::DivisionByZero` if `m` is zero.
+fn check_nonzero<U: UintTrait>(m: &U) -> Result<(), UintError> {
+    if m.ct_eq(&U::default()) == U::Limb::ONE {
+        return Err(UintError::DivisionByZero);
+    }
+    Ok(())
+}
+
+/// Note that `==` is not constant time and also compares the number of limbs.
+/// Use `UintTrait::ct_eq` to compare secret values.
+///
+/// The limbs are zeroized when the Uint is dropped or cleared.
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
-pub struct Uint<T> {
+pub struct Uint<T: Limb> {
     pub(crate) digits: Vec<T>,
 }
 
 /// Trait defining all public functions on Uints
-/// Operators are implemented with macros `impl_add`, `impl_mul`.
+/// Operators are implemented with macros `impl_add`, `impl_sub`, `impl_mul`.
 pub trait UintTrait: Default + PartialEq + Eq + Clone + FromStr {
+    /// The limb type. Constant time flags (0 or 1) are returned as limbs.
+    type Limb: Limb;
+
+    /// Set the value to zero and overwrite the old limbs in memory.
+    /// The writes are not optimised away.
     fn clear(&mut self);
 
+    /// The number of bits that can be stored, i.e. `limbs * BITS`.
+    /// This depends only on the number of limbs, not on the value.
+    fn bit_width(&self) -> usize;
+
     fn encode(&mut self, s: &str) -> Result<&Self, UintError>;
     fn decode(&self) -> Result<String, UintError>;
+    /// Like `decode`, but keeps all leading zeros such that the length of
+    /// the string only depends on the number of limbs.
+    fn decode_padded(&self) -> Result<String, UintError>;
+
+    /// Read a string of digits in base `radix` without prefix, e.g.
+    /// `from_str_radix("1234", 10)`. Letters are accepted in both cases.
+    ///
+    /// # Panics
+    /// If `radix` is not in the range `[2, 36]`.
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, UintError>;
+    /// Write the value in base `radix` without prefix, using lower case
+    /// letters. Zero is written as `0`.
+    ///
+    /// # Panics
+    /// If `radix` is not in the range `[2, 36]`.
+    fn to_str_radix(&self, radix: u32) -> String;
+
+    /// Return 1 if `self == other` and 0 otherwise, in constant time.
+    /// Unlike `==`, numbers with a different limb count but the same value
+    /// are equal.
+    fn ct_eq(&self, other: &Self) -> Self::Limb;
+    /// Return 1 if `self >= other` and 0 otherwise, in constant time.
+    fn ct_gte(&self, other: &Self) -> Self::Limb;
+
+    /// Return 1 if `self < other` and 0 otherwise, in constant time.
+    fn ct_lt(&self, other: &Self) -> Self::Limb {
+        self.ct_gte(other) ^ Self::Limb::ONE
+    }
+
+    /// Return 1 if `self > other` and 0 otherwise, in constant time.
+    fn ct_gt(&self, other: &Self) -> Self::Limb {
+        other.ct_lt(self)
+    }
+
+    /// Set `self = other` if `choice == 1` and leave it untouched if
+    /// `choice == 0`, in constant time.
+    fn conditional_assign(&mut self, other: &Self, choice: Self::Limb);
+    /// Swap `a` and `b` if `choice == 1` and leave them untouched if
+    /// `choice == 0`, in constant time.
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: Self::Limb);
+
+    /// Return `b` if `choice == 1` and `a` if `choice == 0`, in constant time.
+    fn conditional_select(a: &Self, b: &Self, choice: Self::Limb) -> Self {
+        let mut r = a.clone();
+        r.conditional_assign(b, choice);
+        r
+    }
+
+    /// Read a big-endian byte string.
+    fn from_be_bytes(bytes: &[u8]) -> Result<Self, UintError>;
+    /// Read a little-endian byte string.
+    fn from_le_bytes(bytes: &[u8]) -> Result<Self, UintError>;
+    /// Write all limbs as big-endian bytes, including leading zeros.
+    fn to_be_bytes(&self) -> Vec<u8>;
+    /// Write all limbs as little-endian bytes, including trailing zeros.
+    fn to_le_bytes(&self) -> Vec<u8>;
+
+    /// Write the value as exactly `len` big-endian bytes.
+    /// Fails with `UintError::Overflow` if the value doesn't fit.
+    fn to_be_bytes_padded(&self, len: usize) -> Result<Vec<u8>, UintError> {
+        let mut r = self.to_le_bytes_padded(len)?;
+        r.reverse();
+        Ok(r)
+    }
+
+    /// Write the value as exactly `len` little-endian bytes.
+    /// Fails with `UintError::Overflow` if the value doesn't fit.
+    fn to_le_bytes_padded(&self, len: usize) -> Result<Vec<u8>, UintError>;
 
     fn add_(&self, other: &Self) -> Self;
+    /// Return `(self - other, borrow)` where `borrow` is 1 if `self < other`
+    /// and 0 otherwise. The difference wraps around in that case.
+    fn sub_(&self, other: &Self) -> (Self, Self::Limb);
     fn mul_(&self, other: &Self) -> Self;
+    /// Compute `self * self`. This is faster than `self.mul_(self)`.
+    fn sqr_(&self) -> Self;
     fn mod_(&self, modulus: &Self) -> Self;
-    fn pow(&self, modulus: u64) -> Self;
+    fn pow(&self, exp: u64) -> Self;
+    /// Compute `self ^ exp % modulus`. `exp` and `modulus` can be secret,
+    /// the running time only depends on the number of limbs.
     fn pow_mod_(&self, exp: &Self, modulus: &Self) -> Self;
     fn mul_mod_(&self, other: &Self, modulus: &Self) -> Self;
     fn add_mod_(&self, other: &Self, modulus: &Self) -> Self;
 
-    // TODO: return error?
+    /// Like `mod_`, but fails with `UintError::DivisionByZero` instead of
+    /// panicking.
+    fn try_mod_(&self, modulus: &Self) -> Result<Self, UintError> {
+        check_nonzero(modulus)?;
+        Ok(self.mod_(modulus))
+    }
+
+    /// Like `pow_mod_`, but fails with `UintError::DivisionByZero` instead of
+    /// panicking.
+    fn try_pow_mod_(&self, exp: &Self, modulus: &Self) -> Result<Self, UintError> {
+        check_nonzero(modulus)?;
+        Ok(self.pow_mod_(exp, modulus))
+    }
+
+    /// Like `mul_mod_`, but fails with `UintError::DivisionByZero` instead of
+    /// panicking.
+    fn try_mul_mod_(&self, other: &Self, modulus: &Self) -> Result<Self, UintError> {
+        check_nonzero(modulus)?;
+        Ok(self.mul_mod_(other, modulus))
+    }
+
+    /// Like `add_mod_`, but fails with `UintError::NotReduced` instead of
+    /// panicking.
+    fn try_add_mod_(&self, other: &Self, modulus: &Self) -> Result<Self, UintError> {
+        if self.ct_lt(modulus) & other.ct_lt(modulus) != Self::Limb::ONE {
+            return Err(UintError::NotReduced);
+        }
+        Ok(self.add_mod_(other, modulus))
+    }
+
+    /// Get the value as a hex string, see `decode`.
+    ///
+    /// # Panics
+    /// If `decode` fails.
     fn to_str(&self) -> String {
         match self.decode() {
             Ok(s) => s,
-            Err(_) => "".to_string(),
+            Err(e) => panic!("{}", e),
         }
     }
 }
@@ -45,30 +211,67 @@ pub trait UintTrait: Default + PartialEq + Eq + Clone + FromStr {
 #[macro_export]
 macro_rules! impl_add {
     ($($t:ty)*) => ($(
-        impl Add<$t> for $t {
+        impl ::std::ops::Add<$t> for $t {
             type Output = $t;
 
             #[inline]
             fn add(self, other: $t) -> $t {
-                self.add_(&other)
+                $crate::base::UintTrait::add_(&self, &other)
             }
         }
 
-        impl<'a> Add<&'a $t> for $t {
+        impl<'a> ::std::ops::Add<&'a $t> for $t {
             type Output = $t;
 
             #[inline]
             fn add(self, other: &$t) -> $t {
-                self.add_(other)
+                $crate::base::UintTrait::add_(&self, other)
             }
         }
 
-        impl<'a, 'b> Add<&'b $t> for &'a $t {
+        impl<'a, 'b> ::std::ops::Add<&'b $t> for &'a $t {
             type Output = $t;
 
             #[inline]
             fn add(self, other: &$t) -> $t {
-                self.add_(other)
+                $crate::base::UintTrait::add_(self, other)
+            }
+        }
+    )*)
+}
+
+// ===================== Implement - ===========================
+// let c = &a - &b;
+// let c = a - &b;
+// let c = a - b;
+// The difference wraps around if b > a. Use `sub_` to get the borrow.
+#[macro_export]
+macro_rules! impl_sub {
+    ($($t:ty)*) => ($(
+        impl ::std::ops::Sub<$t> for $t {
+            type Output = $t;
+
+            #[inline]
+            fn sub(self, other: $t) -> $t {
+                $crate::base::UintTrait::sub_(&self, &other).0
+            }
+        }
+
+        impl<'a> ::std::ops::Sub<&'a $t> for $t {
+            type Output = $t;
+
+            #[inline]
+            fn sub(self, other: &$t) -> $t {
+                $crate::base::UintTrait::sub_(&self, other).0
+            }
+        }
+
+        impl<'a, 'b> ::std::ops::Sub<&'b $t> for &'a $t {
+            type Output = $t;
+
+            #[inline]
+            fn sub(self, other: &$t) -> $t {
+                $crate::base::UintTrait::sub_(self, other).0
             }
         }
     )*)
@@ -81,30 +284,30 @@ macro_rules! impl_add {
 #[macro_export]
 macro_rules! impl_mul {
     ($($t:ty)*) => ($(
-        impl Mul<$t> for $t {
+        impl ::std::ops::Mul<$t> for $t {
             type Output = $t;
 
             #[inline]
             fn mul(self, other: $t) -> $t {
-                self.mul_(&other)
+                $crate::base::UintTrait::mul_(&self, &other)
             }
         }
 
-        impl<'a> Mul<&'a $t> for $t {
+        impl<'a> ::std::ops::Mul<&'a $t> for $t {
             type Output = $t;
 
             #[inline]
             fn mul(self, other: &$t) -> $t {
-                self.mul_(other)
+                $crate::base::UintTrait::mul_(&self, other)
             }
         }
 
-        impl<'a, 'b> Mul<&'b $t> for &'a $t {
+        impl<'a, 'b> ::std::ops::Mul<&'b $t> for &'a $t {
             type Output = $t;
 
             #[inline]
             fn mul(self, other: &$t) -> $t {
-                self.mul_(other)
+                $crate::base::UintTrait::mul_(self, other)
             }
         }
     )*)
@@ -117,50 +320,43 @@ macro_rules! impl_mul {
 #[macro_export]
 macro_rules! impl_mod {
     ($($t:ty)*) => ($(
-        impl Rem<$t> for $t {
+        impl ::std::ops::Rem<$t> for $t {
             type Output = $t;
 
             #[inline]
             fn rem(self, modulus: $t) -> $t {
-                self.mod_(&modulus)
+                $crate::base::UintTrait::mod_(&self, &modulus)
             }
         }
 
-        impl<'a> Rem<&'a $t> for $t {
+        impl<'a> ::std::ops::Rem<&'a $t> for $t {
             type Output = $t;
 
             #[inline]
             fn rem(self, modulus: &$t) -> $t {
-                self.mod_(modulus)
+                $crate::base::UintTrait::mod_(&self, modulus)
             }
         }
 
-        impl<'a, 'b> Rem<&'b $t> for &'a $t {
+        impl<'a, 'b> ::std::ops::Rem<&'b $t> for &'a $t {
             type Output = $t;
 
             #[inline]
             fn rem(self, modulus: &$t) -> $t {
-                self.mod_(modulus)
+                $crate::base::UintTrait::mod_(self, modulus)
             }
         }
     )*)
 }
 
-// ============== TODO: stack allocated cuint ===============
-
+// ============== Stack allocated cuint ===============
+// create_cuint!(CUint32_256, 256, u32);
+//
+// Defines `CUint32_256` as `FixedUint<256, u32>`, a fixed-width, stack
+// allocated unsigned integer with 256 limbs. See `fixed` for details.
 #[macro_export]
 macro_rules! create_cuint {
     ($name:ident, $size:expr, $limb_type:ident) => {
-        #[derive(Clone, Copy)]
-        pub struct $name {
-            pub(crate) digits: [$limb_type; $size],
-        }
-        impl Default for $name {
-            fn default() -> Self {
-                Self {
-                    digits: [0 as $limb_type; $size],
-                }
-            }
-        }
+        pub type $name = $crate::fixed::FixedUint<$size, $limb_type>;
     };
 }
@@ -0,0 +1,8 @@
+//!
+//! The `Uint<u64>` implementation and its constants.
+//!
+//! All limb types share one implementation in `uint` now. This module is
+//! kept so that `cuint::cuint64` paths keep working.
+//!
+
+pub use uint::*;
@@ -0,0 +1,354 @@
+//!
+//! Fixed-width, stack allocated unsigned integers.
+//!
+//! `FixedUint<N, L>` holds exactly `N` limbs of type `L` in little-endian
+//! order. All operations wrap around at the width of the type, i.e. they are
+//! computed modulo `2^(N * bits(L))`, and never allocate memory on the heap
+//! (except for encoding and decoding strings).
+//!
+//! Because `{2 * N}` can't be written as a const generic argument on stable
+//! Rust yet, the target width of conversions is a separate const parameter
+//! that is checked at compile time.
+//!
+//! Arithmetic is implemented for all limb types with the algorithms in
+//! `algorithms`.
+//!
+
+use std::fmt;
+use std::str::FromStr;
+
+use algorithms::*;
+use base::*;
+use uint::DEFAULT_WINDOW_SIZE;
+use util::*;
+
+/// A fixed-width unsigned integer with `N` limbs of type `L`.
+///
+/// The type is `Copy`, so it can't implement `Drop` and its limbs are
+/// **not** zeroized when a value goes out of scope. `clear()` wipes one
+/// value explicitly. A `SecretUint` wipes the value it owns on drop, but
+/// every copy made before wrapping, including the one moved into
+/// `SecretUint::new`, stays in memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedUint<const N: usize, L: Limb> {
+    pub(crate) digits: [L; N],
+}
+
+impl<const N: usize, L: Limb> Default for FixedUint<N, L> {
+    fn default() -> Self {
+        Self {
+            digits: [L::default(); N],
+        }
+    }
+}
+
+impl<const N: usize, L: Limb> FixedUint<N, L> {
+    /// Zero-extend to `M >= N` limbs.
+    /// `M < N` is a compile time error, use `truncate` for that.
+    pub fn resize<const M: usize>(&self) -> FixedUint<M, L> {
+        const { assert!(M >= N, "resize can't drop limbs, use truncate") };
+        let mut r = FixedUint::<M, L>::default();
+        r.digits[..N].copy_from_slice(&self.digits);
+        r
+    }
+
+    /// Keep the lower `M <= N` limbs, i.e. reduce modulo `2^(M * bits(L))`.
+    /// `M > N` is a compile time error, use `resize` for that.
+    pub fn truncate<const M: usize>(&self) -> FixedUint<M, L> {
+        const { assert!(M <= N, "truncate can't add limbs, use resize") };
+        let mut r = FixedUint::<M, L>::default();
+        r.digits.copy_from_slice(&self.digits[..M]);
+        r
+    }
+
+    /// Return the full product `self * other` with `M == 2 * N` limbs.
+    /// Any other `M` is a compile time error.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    ///     let a = FixedUint::<4, u32>::from_str("0x123").unwrap();
+    ///     let b = FixedUint::<4, u32>::from_str("0x456").unwrap();
+    ///     let c: FixedUint<8, u32> = a.widening_mul(&b);
+    /// ```
+    pub fn widening_mul<const M: usize>(&self, other: &Self) -> FixedUint<M, L> {
+        const { assert!(M == 2 * N, "widening_mul needs twice the limbs") };
+        let mut r = FixedUint::<M, L>::default();
+        mul_slice(&self.digits, &other.digits, &mut r.digits);
+        r
+    }
+}
+
+impl<const N: usize, L: Limb> FromStr for FixedUint<N, L> {
+    type Err = UintError;
+
+    /// Read a hex (`0x`), octal (`0o`), binary (`0b`) or decimal string.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (digits, radix) = split_radix_prefix(s);
+        Self::from_str_radix(digits, radix)
+            .map_err(|e| with_prefix_offset(e, s.len() - digits.len()))
+    }
+}
+
+/// Implement FixedUint<N, L> for all limb types L.
+/// All operations wrap around at `2^(N * bits(L))` and don't allocate.
+impl<const N: usize, L: Limb> UintTrait for FixedUint<N, L> {
+    type Limb = L;
+
+    /// Read a hex string of the form "0xdeadbeef".
+    /// Fails if the value doesn't fit into `N` limbs.
+    /// `self` is not changed if that happens.
+    fn encode(&mut self, s: &str) -> Result<&Self, UintError> {
+        let mut r = Self::default();
+        let res = encode_hex_slice(s, &mut r.digits);
+        if res.is_ok() {
+            *self = r;
+        }
+        r.clear();
+        res?;
+        Ok(self)
+    }
+
+    /// Get the value as a hex string of the form `0xdeadbeef`.
+    fn decode(&self) -> Result<String, UintError> {
+        Ok(decode_hex(&self.digits))
+    }
+
+    /// Get the value as a hex string with `BITS / 4` digits per limb,
+    /// e.g. `0x00000123`.
+    fn decode_padded(&self) -> Result<String, UintError> {
+        Ok(decode_hex_padded(&self.digits))
+    }
+
+    /// Fails with `UintError::Overflow` if the value doesn't fit into `N`
+    /// limbs.
+    fn from_be_bytes(bytes: &[u8]) -> Result<Self, UintError> {
+        let mut r = Self::default();
+        if limbs_from_le_bytes(bytes.iter().rev(), &mut r.digits) != 0 {
+            return Err(UintError::Overflow);
+        }
+        Ok(r)
+    }
+
+    /// Fails with `UintError::Overflow` if the value doesn't fit into `N`
+    /// limbs.
+    fn from_le_bytes(bytes: &[u8]) -> Result<Self, UintError> {
+        let mut r = Self::default();
+        if limbs_from_le_bytes(bytes.iter(), &mut r.digits) != 0 {
+            return Err(UintError::Overflow);
+        }
+        Ok(r)
+    }
+
+    fn to_be_bytes(&self) -> Vec<u8> {
+        let mut r = self.to_le_bytes();
+        r.reverse();
+        r
+    }
+
+    fn to_le_bytes(&self) -> Vec<u8> {
+        let mut r = vec![0u8; N * L::BITS / 8];
+        limbs_to_le_bytes(&self.digits, &mut r);
+        r
+    }
+
+    fn to_le_bytes_padded(&self, len: usize) -> Result<Vec<u8>, UintError> {
+        let mut r = vec![0u8; len];
+        if limbs_to_le_bytes(&self.digits, &mut r) != 0 {
+            return Err(UintError::Overflow);
+        }
+        Ok(r)
+    }
+
+    /// Fails with `UintError::Overflow` if the value doesn't fit into `N`
+    /// limbs.
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, UintError> {
+        assert_radix(radix);
+        let mut r = Self::default();
+        parse_radix_slice(s.as_bytes(), radix, &mut r.digits)?;
+        Ok(r)
+    }
+
+    fn to_str_radix(&self, radix: u32) -> String {
+        assert_radix(radix);
+        to_radix_string(&self.digits, radix)
+    }
+
+    fn ct_eq(&self, other: &Self) -> L {
+        eq_slice(&self.digits, &other.digits)
+    }
+
+    fn ct_gte(&self, other: &Self) -> L {
+        gte_slice(&self.digits, &other.digits)
+    }
+
+    fn conditional_assign(&mut self, other: &Self, choice: L) {
+        cassign_slice(&mut self.digits, &other.digits, choice);
+    }
+
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: L) {
+        cswap_slice(&mut a.digits, &mut b.digits, choice);
+    }
+
+    fn add_(&self, other: &Self) -> Self {
+        let mut r = *self;
+        add_assign_slice(&mut r.digits, &other.digits);
+        r
+    }
+
+    /// The difference wraps around if `other > self`, which is signalled by a
+    /// borrow of 1.
+    fn sub_(&self, other: &Self) -> (Self, L) {
+        let mut r = *self;
+        let borrow = sub_assign_slice(&mut r.digits, &other.digits);
+        (r, borrow)
+    }
+
+    fn mul_(&self, other: &Self) -> Self {
+        let mut r = Self::default();
+        mul_slice(&self.digits, &other.digits, &mut r.digits);
+        r
+    }
+
+    /// Compute `self * self mod 2^(N * bits(L))`.
+    fn sqr_(&self) -> Self {
+        let mut r = Self::default();
+        sqr_slice(&self.digits, &mut r.digits);
+        r
+    }
+
+    /// # Panics
+    /// If `modulus` is zero.
+    fn mod_(&self, modulus: &Self) -> Self {
+        assert_nonzero(&modulus.digits);
+        let mut r = Self::default();
+        mod_slice(&self.digits, &modulus.digits, &mut r.digits);
+        r
+    }
+
+    /// Compute `self ^ exp mod 2^(N * bits(L))` for a **public** exponent.
+    fn pow(&self, exp: u64) -> Self {
+        let mut r = Self::default();
+        let mut tmp = [L::ZERO; N];
+        pow_slice(&self.digits, exp, &mut r.digits, &mut tmp);
+        zeroize_slice(&mut tmp);
+        r
+    }
+
+    /// # Panics
+    /// If `modulus` is zero.
+    fn pow_mod_(&self, exp: &Self, modulus: &Self) -> Self {
+        assert_nonzero(&modulus.digits);
+        let mut r = Self::default();
+        // pow_mod_barrett_scratch_len(N, w) = (2^w + 10) * N + 10 limbs,
+        // which is at most (2^w + 20) * N for N >= 1.
+        let mut scratch = [[L::ZERO; N]; (1 << DEFAULT_WINDOW_SIZE) + 20];
+        let len = pow_mod_barrett_scratch_len(N, DEFAULT_WINDOW_SIZE);
+        pow_mod_barrett_slice(
+            &self.digits,
+            &exp.digits,
+            &modulus.digits,
+            DEFAULT_WINDOW_SIZE,
+            &mut r.digits,
+            &mut scratch.as_flattened_mut()[..len],
+        );
+        zeroize_slice(scratch.as_flattened_mut());
+        r
+    }
+
+    /// The single product is reduced with `mod_slice`. Barrett reduction
+    /// would first need a division of the same size to compute `mu`.
+    ///
+    /// # Panics
+    /// If `modulus` is zero.
+    fn mul_mod_(&self, other: &Self, modulus: &Self) -> Self {
+        assert_nonzero(&modulus.digits);
+        let mut r = Self::default();
+        let mut scratch = [[L::ZERO; N]; 2];
+        mul_mod_slice(
+            &self.digits,
+            &other.digits,
+            &modulus.digits,
+            &mut r.digits,
+            scratch.as_flattened_mut(),
+        );
+        zeroize_slice(scratch.as_flattened_mut());
+        r
+    }
+
+    /// # Panics
+    /// If `self` or `other` is not smaller than `modulus`.
+    fn add_mod_(&self, other: &Self, modulus: &Self) -> Self {
+        assert_reduced(&self.digits, &modulus.digits);
+        assert_reduced(&other.digits, &modulus.digits);
+        let mut r = Self::default();
+        add_mod_slice(&self.digits, &other.digits, &modulus.digits, &mut r.digits);
+        r
+    }
+
+    fn bit_width(&self) -> usize {
+        N * L::BITS
+    }
+
+    /// Set all limbs to zero.
+    /// The writes are not optimised away.
+    fn clear(&mut self) {
+        zeroize_slice(&mut self.digits);
+    }
+}
+
+// `impl_add!` and friends don't work with generic types, so this does the same
+// for all FixedUint<N, L>.
+macro_rules! impl_fixed_op {
+    ($tr:ident, $f:ident, $op:ident $(. $field:tt)*) => {
+        impl<const N: usize, L: Limb> ::std::ops::$tr<FixedUint<N, L>> for FixedUint<N, L> {
+            type Output = FixedUint<N, L>;
+
+            #[inline]
+            fn $f(self, other: FixedUint<N, L>) -> FixedUint<N, L> {
+                self.$op(&other)$(.$field)*
+            }
+        }
+
+        impl<'a, const N: usize, L: Limb> ::std::ops::$tr<&'a FixedUint<N, L>> for FixedUint<N, L> {
+            type Output = FixedUint<N, L>;
+
+            #[inline]
+            fn $f(self, other: &FixedUint<N, L>) -> FixedUint<N, L> {
+                self.$op(other)$(.$field)*
+            }
+        }
+
+        impl<'a, 'b, const N: usize, L: Limb> ::std::ops::$tr<&'b FixedUint<N, L>>
+            for &'a FixedUint<N, L>
+        {
+            type Output = FixedUint<N, L>;
+
+            #[inline]
+            fn $f(self, other: &FixedUint<N, L>) -> FixedUint<N, L> {
+                self.$op(other)$(.$field)*
+            }
+        }
+    };
+}
+
+impl_fixed_op!(Add, add, add_);
+impl_fixed_op!(Sub, sub, sub_.0);
+impl_fixed_op!(Mul, mul, mul_);
+impl_fixed_op!(Rem, rem, mod_);
+
+// format!("{}", a), format!("{:#066x}", a), ...
+macro_rules! impl_fixed_fmt {
+    ($tr:ident, $radix:expr, $upper:expr, $prefix:expr) => {
+        impl<const N: usize, L: Limb> fmt::$tr for FixedUint<N, L> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt_radix(&self.digits, $radix, $upper, $prefix, f)
+            }
+        }
+    };
+}
+
+impl_fixed_fmt!(Display, 10, false, "");
+impl_fixed_fmt!(LowerHex, 16, false, "0x");
+impl_fixed_fmt!(UpperHex, 16, true, "0x");
+impl_fixed_fmt!(Octal, 8, false, "0o");
+impl_fixed_fmt!(Binary, 2, false, "0b");
@@ -6,5 +6,12 @@
 
 #[macro_use]
 pub mod base;
+mod algorithms;
+pub mod barrett;
 pub mod cuint32;
+pub mod cuint64;
+pub mod fixed;
+pub mod montgomery;
+pub mod secret;
+pub mod uint;
 pub mod util;
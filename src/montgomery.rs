@@ -0,0 +1,169 @@
+//!
+//! Montgomery arithmetic modulo an odd modulus `m`.
+//!
+//! A residue `x` is represented by its Montgomery form `x * R mod m` with
+//! `R = 2^(BITS * n)` for a modulus with `n` limbs. Multiplying two values in
+//! Montgomery form with `mont_mul` only needs multiplications and additions,
+//! no division. This makes repeated multiplications, e.g. in `pow_mod`, much
+//! faster than `UintTrait::mul_mod_`.
+//!
+//! `MontgomeryParams` holds the precomputed values for one modulus. The
+//! modulus is considered public, all other values are processed in constant
+//! time.
+//!
+
+use std::cmp::min;
+
+use algorithms::*;
+use base::*;
+use uint::DEFAULT_WINDOW_SIZE;
+use util::*;
+
+/// Precomputed values for Montgomery arithmetic modulo an odd `m`.
+#[derive(Debug, Clone)]
+pub struct MontgomeryParams<T: Limb> {
+    /// The modulus `m`.
+    modulus: Uint<T>,
+    /// `-m^-1 mod 2^BITS`
+    m_inv: T,
+    /// `R mod m`, i.e. the Montgomery form of 1.
+    r: Uint<T>,
+    /// `R^2 mod m`, used to convert into Montgomery form.
+    r2: Uint<T>,
+}
+
+impl<T: Limb> MontgomeryParams<T> {
+    /// Precompute the Montgomery parameters for `modulus`.
+    ///
+    /// Fails with `UintError::EvenModulus` if `modulus` is even (or zero).
+    pub fn new(modulus: &Uint<T>) -> Result<Self, UintError> {
+        let m = &modulus.digits;
+        // This is ok, the modulus is public.
+        if m.is_empty() || m[0] & T::ONE == T::ZERO {
+            return Err(UintError::EvenModulus);
+        }
+        let n = m.len();
+
+        // R and R^2 are a one followed by n and 2n zero limbs.
+        let mut r = Scratch::new(2 * n + 1);
+        r[n] = T::ONE;
+        let r1 = mod_generic(&r[..n + 1], m);
+        r[n] = T::ZERO;
+        r[2 * n] = T::ONE;
+        let r2 = mod_generic(&r, m);
+
+        Ok(Self {
+            modulus: modulus.clone(),
+            m_inv: mont_inv_limb(m[0]),
+            r: Uint { digits: r1 },
+            r2: Uint { digits: r2 },
+        })
+    }
+
+    /// The modulus `m`.
+    pub fn modulus(&self) -> &Uint<T> {
+        &self.modulus
+    }
+
+    /// The Montgomery form of 1, i.e. `R mod m`.
+    pub fn one(&self) -> Uint<T> {
+        self.r.clone()
+    }
+
+    /// Copy `a` into a buffer with as many limbs as the modulus.
+    ///
+    /// # Panics
+    /// If `a` is not smaller than the modulus.
+    fn padded(&self, a: &Uint<T>) -> Scratch<T> {
+        let m = &self.modulus.digits;
+        assert_reduced(&a.digits, m);
+        let mut r = Scratch::new(m.len());
+        // a < m, so any limbs above m.len() are zero.
+        let len = min(a.digits.len(), m.len());
+        r[..len].copy_from_slice(&a.digits[..len]);
+        r
+    }
+
+    /// Compute `a * b * R^-1 mod m` into a new Uint.
+    /// `a` and `b` MUST have as many limbs as the modulus.
+    fn mul_padded(&self, a: &[T], b: &[T]) -> Uint<T> {
+        let m = &self.modulus.digits;
+        let mut r = vec![T::ZERO; m.len()];
+        let mut t = Scratch::new(m.len() + 2);
+        mont_mul_slice(a, b, m, self.m_inv, &mut r, &mut t);
+        Uint { digits: r }
+    }
+
+    /// Convert `a` into Montgomery form, i.e. compute `a * R mod m`.
+    /// `a` doesn't have to be reduced.
+    /// The result has as many limbs as the modulus.
+    pub fn to_montgomery(&self, a: &Uint<T>) -> Uint<T> {
+        let reduced = Uint {
+            digits: mod_generic(&a.digits, &self.modulus.digits),
+        };
+        self.mul_padded(&reduced.digits, &self.r2.digits)
+    }
+
+    /// Convert `a` out of Montgomery form, i.e. compute `a * R^-1 mod m`.
+    /// The result has as many limbs as the modulus.
+    ///
+    /// # Panics
+    /// If `a` is not smaller than the modulus.
+    pub fn from_montgomery(&self, a: &Uint<T>) -> Uint<T> {
+        let a = self.padded(a);
+        let mut one = Scratch::new(a.len());
+        one[0] = T::ONE;
+        self.mul_padded(&a, &one)
+    }
+
+    /// Multiply two values in Montgomery form, i.e. compute
+    /// `a * b * R^-1 mod m`.
+    /// The result has as many limbs as the modulus.
+    ///
+    /// # Panics
+    /// If `a` or `b` is not smaller than the modulus.
+    pub fn mont_mul(&self, a: &Uint<T>, b: &Uint<T>) -> Uint<T> {
+        self.mul_padded(&self.padded(a), &self.padded(b))
+    }
+
+    /// Square a value in Montgomery form, i.e. compute `a * a * R^-1 mod m`.
+    /// The result has as many limbs as the modulus.
+    ///
+    /// # Panics
+    /// If `a` is not smaller than the modulus.
+    pub fn mont_sqr(&self, a: &Uint<T>) -> Uint<T> {
+        let m = &self.modulus.digits;
+        let a = self.padded(a);
+        let mut r = vec![T::ZERO; m.len()];
+        let mut t = Scratch::new(2 * m.len() + 1);
+        mont_sqr_slice(&a, m, self.m_inv, &mut r, &mut t);
+        Uint { digits: r }
+    }
+
+    /// Compute `base ^ exp mod m` with Montgomery multiplications.
+    ///
+    /// `base` doesn't have to be reduced and is not in Montgomery form, the
+    /// result isn't either. The exponent is considered secret, this uses the
+    /// same fixed window exponentiation as `UintTrait::pow_mod_`.
+    /// The result has as many limbs as the modulus.
+    pub fn pow_mod(&self, base: &Uint<T>, exp: &Uint<T>) -> Uint<T> {
+        let m = &self.modulus.digits;
+        let n = m.len();
+        let a = self.to_montgomery(base);
+        let mut r = Uint {
+            digits: vec![T::ZERO; n],
+        };
+        let mut scratch = Scratch::new(((1 << DEFAULT_WINDOW_SIZE) + 4) * n + 1);
+        mont_pow_slice(
+            &a.digits,
+            &exp.digits,
+            m,
+            self.m_inv,
+            &self.r.digits,
+            DEFAULT_WINDOW_SIZE,
+            &mut r.digits,
+            &mut scratch,
+        );
+        self.from_montgomery(&r)
+    }
+}
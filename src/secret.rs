@@ -0,0 +1,219 @@
+//!
+//! A wrapper for secret values such as private keys.
+//!
+//! `SecretUint<U>` wraps any `UintTrait` type. Its `Debug` and `Display`
+//! implementations only print the bit width, so the value can't end up in
+//! logs or panic messages by accident. The value can only be read with an
+//! explicit call to `expose_secret`.
+//!
+//! All arithmetic of `UintTrait` is available on the wrapper and returns
+//! wrapped values again.
+//!
+//! The value is cleared when the wrapper is dropped. For `Copy` types such
+//! as `FixedUint` this only covers the wrapped value, not copies made before
+//! it was wrapped.
+//!
+
+use std::fmt;
+
+use base::*;
+
+/// A Uint whose value is not printed.
+#[derive(Clone, Default)]
+pub struct SecretUint<U: UintTrait> {
+    value: U,
+}
+
+macro_rules! forward_binary {
+    ($($(#[$doc:meta])* $f:ident),*) => {$(
+        $(#[$doc])*
+        pub fn $f(&self, other: &Self) -> Self {
+            Self::new(self.value.$f(&other.value))
+        }
+    )*};
+}
+
+macro_rules! forward_mod {
+    ($($(#[$doc:meta])* $f:ident),*) => {$(
+        $(#[$doc])*
+        pub fn $f(&self, other: &Self, modulus: &Self) -> Self {
+            Self::new(self.value.$f(&other.value, &modulus.value))
+        }
+    )*};
+}
+
+macro_rules! forward_try_mod {
+    ($($(#[$doc:meta])* $f:ident),*) => {$(
+        $(#[$doc])*
+        pub fn $f(&self, other: &Self, modulus: &Self) -> Result<Self, UintError> {
+            self.value.$f(&other.value, &modulus.value).map(Self::new)
+        }
+    )*};
+}
+
+macro_rules! forward_cmp {
+    ($($(#[$doc:meta])* $f:ident),*) => {$(
+        $(#[$doc])*
+        pub fn $f(&self, other: &Self) -> U::Limb {
+            self.value.$f(&other.value)
+        }
+    )*};
+}
+
+impl<U: UintTrait> SecretUint<U> {
+    /// Wrap a secret value.
+    pub fn new(value: U) -> Self {
+        Self { value }
+    }
+
+    /// Get the secret value.
+    pub fn expose_secret(&self) -> &U {
+        &self.value
+    }
+
+    /// The number of bits that can be stored. This is not secret.
+    pub fn bit_width(&self) -> usize {
+        self.value.bit_width()
+    }
+
+    /// See `UintTrait::clear`.
+    pub fn clear(&mut self) {
+        self.value.clear();
+    }
+
+    forward_binary!(
+        /// See `UintTrait::add_`.
+        add_,
+        /// See `UintTrait::mul_`.
+        mul_,
+        /// See `UintTrait::mod_`.
+        mod_
+    );
+
+    /// See `UintTrait::sub_`.
+    pub fn sub_(&self, other: &Self) -> (Self, U::Limb) {
+        let (r, borrow) = self.value.sub_(&other.value);
+        (Self::new(r), borrow)
+    }
+
+    /// See `UintTrait::sqr_`.
+    pub fn sqr_(&self) -> Self {
+        Self::new(self.value.sqr_())
+    }
+
+    /// See `UintTrait::pow`. The exponent is **public**.
+    pub fn pow(&self, exp: u64) -> Self {
+        Self::new(self.value.pow(exp))
+    }
+
+    forward_mod!(
+        /// See `UintTrait::pow_mod_`.
+        pow_mod_,
+        /// See `UintTrait::mul_mod_`.
+        mul_mod_,
+        /// See `UintTrait::add_mod_`.
+        add_mod_
+    );
+
+    /// See `UintTrait::try_mod_`.
+    pub fn try_mod_(&self, modulus: &Self) -> Result<Self, UintError> {
+        self.value.try_mod_(&modulus.value).map(Self::new)
+    }
+
+    forward_try_mod!(
+        /// See `UintTrait::try_pow_mod_`.
+        try_pow_mod_,
+        /// See `UintTrait::try_mul_mod_`.
+        try_mul_mod_,
+        /// See `UintTrait::try_add_mod_`.
+        try_add_mod_
+    );
+
+    forward_cmp!(
+        /// See `UintTrait::ct_eq`.
+        ct_eq,
+        /// See `UintTrait::ct_gte`.
+        ct_gte,
+        /// See `UintTrait::ct_lt`.
+        ct_lt,
+        /// See `UintTrait::ct_gt`.
+        ct_gt
+    );
+
+    /// See `UintTrait::conditional_assign`.
+    pub fn conditional_assign(&mut self, other: &Self, choice: U::Limb) {
+        self.value.conditional_assign(&other.value, choice);
+    }
+
+    /// See `UintTrait::conditional_swap`.
+    pub fn conditional_swap(a: &mut Self, b: &mut Self, choice: U::Limb) {
+        U::conditional_swap(&mut a.value, &mut b.value, choice);
+    }
+
+    /// See `UintTrait::conditional_select`.
+    pub fn conditional_select(a: &Self, b: &Self, choice: U::Limb) -> Self {
+        Self::new(U::conditional_select(&a.value, &b.value, choice))
+    }
+}
+
+impl<U: UintTrait> From<U> for SecretUint<U> {
+    fn from(value: U) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<U: UintTrait> Drop for SecretUint<U> {
+    fn drop(&mut self) {
+        self.value.clear();
+    }
+}
+
+impl<U: UintTrait> fmt::Debug for SecretUint<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Not using `debug_struct` because `{:x?}` would print the width in hex.
+        write!(f, "SecretUint {{ bits: {}, .. }}", self.bit_width())
+    }
+}
+
+impl<U: UintTrait> fmt::Display for SecretUint<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<secret: {} bits>", self.bit_width())
+    }
+}
+
+// Same as `impl_add!` and friends, but for SecretUint<U>.
+macro_rules! impl_secret_op {
+    ($tr:ident, $f:ident, $op:ident $(. $field:tt)*) => {
+        impl<U: UintTrait> ::std::ops::$tr<SecretUint<U>> for SecretUint<U> {
+            type Output = SecretUint<U>;
+
+            #[inline]
+            fn $f(self, other: SecretUint<U>) -> SecretUint<U> {
+                self.$op(&other)$(.$field)*
+            }
+        }
+
+        impl<'a, U: UintTrait> ::std::ops::$tr<&'a SecretUint<U>> for SecretUint<U> {
+            type Output = SecretUint<U>;
+
+            #[inline]
+            fn $f(self, other: &SecretUint<U>) -> SecretUint<U> {
+                self.$op(other)$(.$field)*
+            }
+        }
+
+        impl<'a, 'b, U: UintTrait> ::std::ops::$tr<&'b SecretUint<U>> for &'a SecretUint<U> {
+            type Output = SecretUint<U>;
+
+            #[inline]
+            fn $f(self, other: &SecretUint<U>) -> SecretUint<U> {
+                self.$op(other)$(.$field)*
+            }
+        }
+    };
+}
+
+impl_secret_op!(Add, add, add_);
+impl_secret_op!(Sub, sub, sub_.0);
+impl_secret_op!(Mul, mul, mul_);
+impl_secret_op!(Rem, rem, mod_);
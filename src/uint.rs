@@ -0,0 +1,441 @@
+//!
+//! This is a constant time unsigned integer implementation using u16, u32,
+//! or u64 limbs.
+//! It is **work in progress** starting out with some rather slow and generic
+//! implementation.
+//!
+//! The limbs are stored in little-endian representation.
+//! So `0x123456789abcdef` would be stored as `[0x89abcdef, 0x1234567]` in a
+//! `Uint<u32>` and as `[0xcdef, 0x89ab, 0x4567, 0x123]` in a `Uint<u16>`.
+//!
+//! All limb types share the algorithms in `algorithms`.
+//!
+//! Multiplication switches to Karatsuba for operands with at least
+//! `KARATSUBA_THRESHOLD` limbs and to Toom-3 for operands with at least
+//! `TOOM3_THRESHOLD` limbs. Squaring with `sqr_` uses the same thresholds.
+//! See `fixed` for fixed-length versions without dynamic allocations.
+//!
+
+use std::cmp::max;
+use std::fmt;
+use std::str::FromStr;
+
+use algorithms::*;
+use base::*;
+use util::*;
+
+// ===================== Uint<T> implmementaiton ===============
+
+impl<T: Limb> FromStr for Uint<T> {
+    type Err = UintError;
+
+    /// Read a hex (`0x`), octal (`0o`), binary (`0b`) or decimal string.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (digits, radix) = split_radix_prefix(s);
+        Self::from_str_radix(digits, radix)
+            .map_err(|e| with_prefix_offset(e, s.len() - digits.len()))
+    }
+}
+
+/// Implement Uint<T> for all limb types T.
+/// Note that operations are constant time but the digits are heap allocated.
+impl<T: Limb> UintTrait for Uint<T> {
+    type Limb = T;
+
+    /// Read a hex string into a Uint<T>.
+    /// The string MUST be of the form "0xdeadbeef".
+    /// Use `from_str` or `from_str_radix` for other bases.
+    fn encode(&mut self, s: &str) -> Result<&Uint<T>, UintError> {
+        self.clear();
+        self.digits = encode_hex(s)?;
+        Ok(self)
+    }
+
+    /// Get a Uint<T> as a hex string of the form `0xdeadbeef`.
+    /// Zero is written as `0x0`.
+    fn decode(&self) -> Result<String, UintError> {
+        Ok(decode_hex(&self.digits))
+    }
+
+    /// Get the value as a hex string with `BITS / 4` digits per limb,
+    /// e.g. `0x00000123`.
+    fn decode_padded(&self) -> Result<String, UintError> {
+        Ok(decode_hex_padded(&self.digits))
+    }
+
+    /// Read a big-endian byte string into `ceil(len / bytes(T))` limbs.
+    /// This never fails.
+    fn from_be_bytes(bytes: &[u8]) -> Result<Self, UintError> {
+        let mut digits = vec![T::ZERO; bytes.len().div_ceil(T::BITS / 8)];
+        limbs_from_le_bytes(bytes.iter().rev(), &mut digits);
+        Ok(Self { digits })
+    }
+
+    /// Read a little-endian byte string into `ceil(len / bytes(T))` limbs.
+    /// This never fails.
+    fn from_le_bytes(bytes: &[u8]) -> Result<Self, UintError> {
+        let mut digits = vec![T::ZERO; bytes.len().div_ceil(T::BITS / 8)];
+        limbs_from_le_bytes(bytes.iter(), &mut digits);
+        Ok(Self { digits })
+    }
+
+    fn to_be_bytes(&self) -> Vec<u8> {
+        let mut r = self.to_le_bytes();
+        r.reverse();
+        r
+    }
+
+    fn to_le_bytes(&self) -> Vec<u8> {
+        let mut r = vec![0u8; self.digits.len() * T::BITS / 8];
+        limbs_to_le_bytes(&self.digits, &mut r);
+        r
+    }
+
+    fn to_le_bytes_padded(&self, len: usize) -> Result<Vec<u8>, UintError> {
+        let mut r = vec![0u8; len];
+        if limbs_to_le_bytes(&self.digits, &mut r) != 0 {
+            return Err(UintError::Overflow);
+        }
+        Ok(r)
+    }
+
+    /// The number of limbs only depends on the length of the string.
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, UintError> {
+        Ok(Self {
+            digits: encode_radix(s, radix)?,
+        })
+    }
+
+    fn to_str_radix(&self, radix: u32) -> String {
+        assert_radix(radix);
+        to_radix_string(&self.digits, radix)
+    }
+
+    fn ct_eq(&self, other: &Self) -> T {
+        eq_slice(&self.digits, &other.digits)
+    }
+
+    fn ct_gte(&self, other: &Self) -> T {
+        gte_slice(&self.digits, &other.digits)
+    }
+
+    /// If `other` has more limbs than `self`, `self` is zero-extended first,
+    /// independent of `choice`.
+    fn conditional_assign(&mut self, other: &Self, choice: T) {
+        // This is ok, the lengths are not sensitive.
+        if self.digits.len() < other.digits.len() {
+            resize_vec(&mut self.digits, other.digits.len());
+        }
+        cassign_slice(&mut self.digits, &other.digits, choice);
+    }
+
+    /// The shorter of `a` and `b` is zero-extended first, independent of
+    /// `choice`.
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: T) {
+        // This is ok, the lengths are not sensitive.
+        let len = max(a.digits.len(), b.digits.len());
+        resize_vec(&mut a.digits, len);
+        resize_vec(&mut b.digits, len);
+        cswap_slice(&mut a.digits, &mut b.digits, choice);
+    }
+
+    /// Add two Uint<T>.
+    /// This uses a generic, slow addition algorithm at this time.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    ///     let a = Uint::<u32>::from_str("0x123");
+    ///     let b = Uint::<u32>::from_str("0x456");
+    ///     let c = a.add_(&b);
+    /// ```
+    fn add_(&self, other: &Self) -> Self {
+        let res = add_generic(&self.digits, &other.digits);
+        Self { digits: res }
+    }
+
+    /// Subtract two Uint<T>.
+    /// The result has as many limbs as the longer operand and wraps around
+    /// if `other > self`, which is signalled by a borrow of 1.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    ///     let a = Uint::<u32>::from_str("0x456");
+    ///     let b = Uint::<u32>::from_str("0x123");
+    ///     let (c, borrow) = a.sub_(&b);
+    /// ```
+    fn sub_(&self, other: &Self) -> (Self, T) {
+        let (res, borrow) = sub_generic(&self.digits, &other.digits);
+        (Self { digits: res }, borrow)
+    }
+
+    /// Multiply two Uint<T>.
+    /// This uses Karatsuba multiplication above `KARATSUBA_THRESHOLD` limbs
+    /// and Toom-3 above `TOOM3_THRESHOLD` limbs, see `mul_karatsuba_` and
+    /// `mul_toom3_`.
+    ///
+    /// # Example:
+    /// ```rust,ignore
+    ///     let a = Uint::<u32>::from_str("0x123");
+    ///     let b = Uint::<u32>::from_str("0x456");
+    ///     let c = a.mul_(&b);
+    /// ```
+    fn mul_(&self, other: &Self) -> Self {
+        let res = mul_fast_generic(
+            &self.digits,
+            &other.digits,
+            KARATSUBA_THRESHOLD,
+            TOOM3_THRESHOLD,
+        );
+        Self { digits: res }
+    }
+
+    /// Square a Uint<T>.
+    /// Every cross product of two limbs is computed once and doubled, which
+    /// needs about half the limb multiplications of `mul_`. Large operands
+    /// use the same Karatsuba and Toom-3 thresholds as `mul_`.
+    /// The result has `2 * self.digits.len()` limbs.
+    fn sqr_(&self) -> Self {
+        let res = sqr_fast_generic(&self.digits, KARATSUBA_THRESHOLD, TOOM3_THRESHOLD);
+        Self { digits: res }
+    }
+
+    /// Compute `self ^ exp`.
+    ///
+    /// The exponent is a `u64` and considered **public**, i.e. the running
+    /// time depends on `exp`, but not on the value of `self`.
+    /// Use `pow_mod_` for secret exponents.
+    /// The result has `exp * self.digits.len()` limbs (one limb if `exp` is 0).
+    fn pow(&self, exp: u64) -> Self {
+        let res = pow_generic(&self.digits, exp, KARATSUBA_THRESHOLD, TOOM3_THRESHOLD);
+        Self { digits: res }
+    }
+
+    /// Compute `self ^ exp mod modulus` with a window of `DEFAULT_WINDOW_SIZE`
+    /// bits. See `pow_mod_window_` for details.
+    ///
+    /// # Panics
+    /// If `modulus` is zero.
+    fn pow_mod_(&self, exp: &Self, modulus: &Self) -> Self {
+        self.pow_mod_window_(exp, modulus, DEFAULT_WINDOW_SIZE)
+    }
+
+    /// Multiply two Uint<T> modulo `modulus`.
+    /// The running time only depends on the number of limbs of the inputs.
+    /// Use a `BarrettReducer` when multiplying with the same modulus
+    /// repeatedly.
+    /// The result has as many limbs as `modulus`.
+    ///
+    /// # Panics
+    /// If `modulus` is zero.
+    fn mul_mod_(&self, other: &Self, modulus: &Self) -> Self {
+        assert_nonzero(&modulus.digits);
+        let res = mul_mod_generic(&self.digits, &other.digits, &modulus.digits);
+        Self { digits: res }
+    }
+
+    /// Add two Uint<T> modulo `modulus`.
+    /// Both inputs MUST be reduced, i.e. smaller than `modulus`.
+    /// The result has as many limbs as `modulus`.
+    ///
+    /// # Panics
+    /// If `self` or `other` is not smaller than `modulus`.
+    fn add_mod_(&self, other: &Self, modulus: &Self) -> Self {
+        assert_reduced(&self.digits, &modulus.digits);
+        assert_reduced(&other.digits, &modulus.digits);
+        let res = add_mod_generic(&self.digits, &other.digits, &modulus.digits);
+        Self { digits: res }
+    }
+
+    /// Reduce a Uint<T> modulo `modulus`.
+    /// The running time only depends on the number of limbs of `self` and
+    /// `modulus`. The result has as many limbs as `modulus`.
+    ///
+    /// # Panics
+    /// If `modulus` is zero.
+    fn mod_(&self, modulus: &Self) -> Self {
+        assert_nonzero(&modulus.digits);
+        let res = mod_generic(&self.digits, &modulus.digits);
+        Self { digits: res }
+    }
+
+    fn bit_width(&self) -> usize {
+        self.digits.len() * T::BITS
+    }
+
+    /// Clear a Uint<T>, i.e. this Uint<T> == 0 after this operation.
+    /// The allocation is kept, but all limbs in it are zeroized.
+    fn clear(&mut self) {
+        zeroize_vec(&mut self.digits);
+        self.digits.clear();
+    }
+}
+
+impl<T: Limb> Drop for Uint<T> {
+    fn drop(&mut self) {
+        zeroize_vec(&mut self.digits);
+    }
+}
+
+/// The window size used by `pow_mod_`.
+pub const DEFAULT_WINDOW_SIZE: usize = 4;
+
+/// The number of limbs from which on `mul_` uses Karatsuba multiplication.
+pub const KARATSUBA_THRESHOLD: usize = 32;
+
+/// The number of limbs from which on `mul_` uses Toom-3 multiplication.
+pub const TOOM3_THRESHOLD: usize = 128;
+
+impl<T: Limb> Uint<T> {
+    /// Compute `self ^ exp mod modulus` with a fixed window of `window` bits.
+    ///
+    /// The exponent is considered secret. The number of multiplications only
+    /// depends on the number of limbs in `exp` and the table of precomputed
+    /// powers is read with a full scan for every window.
+    /// Larger windows need fewer multiplications but a table with
+    /// `2^window` entries.
+    /// The products are reduced with Barrett reduction (see `barrett`), which
+    /// is precomputed once for `modulus`. The modulus can be secret, the
+    /// running time only depends on its number of limbs, not its value.
+    /// The result has as many limbs as `modulus`.
+    ///
+    /// # Panics
+    /// If `modulus` is zero or `window` is not in `1..=8`.
+    pub fn pow_mod_window_(&self, exp: &Self, modulus: &Self, window: usize) -> Self {
+        assert_nonzero(&modulus.digits);
+        assert!(
+            (1..=8).contains(&window),
+            "the window size must be between 1 and 8"
+        );
+        let res = pow_mod_generic(&self.digits, &exp.digits, &modulus.digits, window);
+        Self { digits: res }
+    }
+
+    /// Multiply two Uint<T> with Karatsuba multiplication.
+    ///
+    /// Operands are split in halves recursively until the shorter one has
+    /// less than `threshold` limbs, which are then multiplied with the
+    /// schoolbook algorithm. A `threshold` larger than the operands always
+    /// uses the schoolbook algorithm. Toom-3 is not used.
+    /// The recursion only depends on the number of limbs, so the running time
+    /// does too.
+    /// The result has `self.digits.len() + other.digits.len()` limbs.
+    ///
+    /// # Panics
+    /// If `threshold` is smaller than 4.
+    pub fn mul_karatsuba_(&self, other: &Self, threshold: usize) -> Self {
+        assert!(threshold >= 4, "the Karatsuba threshold must be at least 4");
+        let res = mul_fast_generic(&self.digits, &other.digits, threshold, usize::MAX);
+        Self { digits: res }
+    }
+
+    /// Multiply two Uint<T> with Toom-3 multiplication.
+    ///
+    /// Balanced operands with at least `threshold` limbs are split in three
+    /// parts, which are evaluated at 5 points, multiplied and interpolated
+    /// again. The interpolation only uses exact divisions by 2 and 3, which
+    /// are computed in constant time. Smaller or unbalanced products use
+    /// Karatsuba above `KARATSUBA_THRESHOLD` limbs, see `mul_karatsuba_`.
+    /// The recursion only depends on the number of limbs, so the running time
+    /// does too.
+    /// The result has `self.digits.len() + other.digits.len()` limbs.
+    pub fn mul_toom3_(&self, other: &Self, threshold: usize) -> Self {
+        let res = mul_fast_generic(&self.digits, &other.digits, KARATSUBA_THRESHOLD, threshold);
+        Self { digits: res }
+    }
+}
+
+impl_add!(Uint<u16>);
+impl_add!(Uint<u32>);
+impl_add!(Uint<u64>);
+impl_sub!(Uint<u16>);
+impl_sub!(Uint<u32>);
+impl_sub!(Uint<u64>);
+impl_mul!(Uint<u16>);
+impl_mul!(Uint<u32>);
+impl_mul!(Uint<u64>);
+impl_mod!(Uint<u16>);
+impl_mod!(Uint<u32>);
+impl_mod!(Uint<u64>);
+
+// ===================== Formatting ===============
+// format!("{}", a), format!("{:#066x}", a), ...
+
+macro_rules! impl_uint_fmt {
+    ($tr:ident, $radix:expr, $upper:expr, $prefix:expr) => {
+        impl<T: Limb> fmt::$tr for Uint<T> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt_radix(&self.digits, $radix, $upper, $prefix, f)
+            }
+        }
+    };
+}
+
+impl_uint_fmt!(Display, 10, false, "");
+impl_uint_fmt!(LowerHex, 16, false, "0x");
+impl_uint_fmt!(UpperHex, 16, true, "0x");
+impl_uint_fmt!(Octal, 8, false, "0o");
+impl_uint_fmt!(Binary, 2, false, "0b");
+
+// ===================== Conversions ===============
+
+/// Convert a Uint<u32> into a Uint<u64>.
+/// Two u32 limbs are combined into one u64 limb, i.e. the result has
+/// `ceil(n / 2)` limbs.
+impl From<&Uint<u32>> for Uint<u64> {
+    fn from(x: &Uint<u32>) -> Self {
+        let digits = x
+            .digits
+            .chunks(2)
+            .map(|c| {
+                let hi = if c.len() == 2 { c[1] } else { 0 };
+                (u64::from(hi) << 32) | u64::from(c[0])
+            })
+            .collect();
+        Self { digits }
+    }
+}
+
+impl From<Uint<u32>> for Uint<u64> {
+    fn from(x: Uint<u32>) -> Self {
+        Self::from(&x)
+    }
+}
+
+/// Convert a Uint<u64> into a Uint<u32>.
+/// Every u64 limb is split into two u32 limbs, i.e. the result has `2n`
+/// limbs.
+impl From<&Uint<u64>> for Uint<u32> {
+    fn from(x: &Uint<u64>) -> Self {
+        let mut digits = Vec::<u32>::with_capacity(2 * x.digits.len());
+        for d in x.digits.iter() {
+            digits.push(*d as u32);
+            digits.push((d >> 32) as u32);
+        }
+        Self { digits }
+    }
+}
+
+impl From<Uint<u64>> for Uint<u32> {
+    fn from(x: Uint<u64>) -> Self {
+        Self::from(&x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::slice;
+
+    #[test]
+    fn test_clear_wipes_buffer() {
+        let mut x = Uint::<u32>::from_str("0xdeadbeefcafebabe0123456789abcdef").unwrap();
+        let (ptr, len) = (x.digits.as_ptr(), x.digits.len());
+        assert!(len > 0);
+        x.clear();
+        // The allocation is kept, the old limbs are now in its spare capacity.
+        assert_eq!(ptr, x.digits.as_ptr());
+        assert!(x.digits.capacity() >= len);
+        let old = unsafe { slice::from_raw_parts(ptr, len) };
+        assert!(old.iter().all(|&d| d == 0), "{:x?}", old);
+    }
+}
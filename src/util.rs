@@ -6,7 +6,9 @@
 //!
 //! TODO: rotate etc.
 
-// TODO: u128
+use std::fmt::{Debug, LowerHex};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Mul, Not, Shl, Shr};
+
 // TODO: pass values (no &)?
 /// The main trait, implemented for u16, u32, u64, and u128
 pub trait CTimeOperations<T> {
@@ -23,16 +25,181 @@ pub trait CTimeOperations<T> {
     /// Return (a + b, carry).
     fn add_with_carry(a: &T, b: &T) -> (T, T);
 
-    /// Return (a * b lower 64 bits, a * b higher 64 bits) if c == 1, (a, 0) if c == 0, and rubbish otherwise.
+    /// Return (a - b, borrow) if c == 1, (a, 0) if c == 0, and rubbish otherwise.
+    fn csub(a: &T, b: &T, c: &T) -> (T, T);
+
+    /// Return (a - b, borrow).
+    fn sub_with_borrow(a: &T, b: &T) -> (T, T);
+
+    /// Return (a * b lower half, a * b higher half) if c == 1, (a, 0) if c == 0, and rubbish otherwise.
     fn cmul(a: &T, b: &T, c: &T) -> (T, T);
 
-    /// Return (a * b lower 64 bits, a * b higher 64 bits).
+    /// Return (a * b lower half, a * b higher half).
     fn mul_with_carry(a: &T, b: &T) -> (T, T);
 
     /// Return (b, a) if c == 1; (a, b) otherwise.
     fn cswap(a: &T, b: &T, c: &T) -> (T, T);
 }
 
+/// Types that can be used as limbs of a big number.
+/// This is implemented for u16, u32, and u64, which have a native
+/// double-width type `Wide`.
+pub trait Limb:
+    Copy
+    + Default
+    + Eq
+    + Debug
+    + LowerHex
+    + CTimeOperations<Self>
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+    + Shl<usize, Output = Self>
+    + Shr<usize, Output = Self>
+{
+    /// The unsigned integer type with twice the bits of this limb.
+    type Wide: Copy
+        + From<Self>
+        + Add<Output = Self::Wide>
+        + Mul<Output = Self::Wide>
+        + Shr<usize, Output = Self::Wide>;
+
+    /// Number of bits in a limb.
+    const BITS: usize;
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// Return the lower half of `w`.
+    fn from_wide(w: Self::Wide) -> Self;
+
+    /// Return the lower `BITS` bits of `x`.
+    fn from_u64(x: u64) -> Self;
+
+    /// Return the lower 64 bits of `x`.
+    fn to_u64(x: Self) -> u64;
+
+    /// Return (a * b + c + d lower half, a * b + c + d higher half).
+    /// This never overflows `Wide`.
+    #[inline]
+    fn mul_add_carry(a: &Self, b: &Self, c: &Self, d: &Self) -> (Self, Self) {
+        let r = Self::Wide::from(*a) * Self::Wide::from(*b)
+            + Self::Wide::from(*c)
+            + Self::Wide::from(*d);
+        (Self::from_wide(r), Self::from_wide(r >> Self::BITS))
+    }
+}
+
+macro_rules! impl_limb {
+    ($t:ty, $wide:ty, $bits:expr) => {
+        impl Limb for $t {
+            type Wide = $wide;
+
+            const BITS: usize = $bits;
+            const ZERO: $t = 0;
+            const ONE: $t = 1;
+
+            #[inline]
+            fn from_wide(w: $wide) -> $t {
+                w as $t
+            }
+
+            #[inline]
+            fn from_u64(x: u64) -> $t {
+                x as $t
+            }
+
+            #[inline]
+            fn to_u64(x: $t) -> u64 {
+                x as u64
+            }
+        }
+    };
+}
+
+impl_limb!(u16, u32, 16);
+impl_limb!(u32, u64, 32);
+impl_limb!(u64, u128, 64);
+
+impl CTimeOperations<u128> for u128 {
+    #[inline]
+    fn equal(a: &u128, b: &u128) -> u128 {
+        let mut x = !(a ^ b);
+        x = x & (x << 64);
+        x = x & (x << 32);
+        x = x & (x << 16);
+        x = x & (x << 8);
+        x = x & (x << 4);
+        x = x & (x << 2);
+        x = x & (x << 1);
+        x >> 127
+    }
+
+    #[inline]
+    fn gte(a: &u128, b: &u128) -> u128 {
+        // There's no wider signed type, so use the borrow of a - b.
+        (a.overflowing_sub(*b).1 as u128) ^ 1
+    }
+
+    #[inline]
+    fn cadd(a: &u128, b: &u128, c: &u128) -> (u128, u128) {
+        let c = (!c).overflowing_add(1).0;
+        let r = a.overflowing_add(b & c);
+        (r.0, r.1 as u128)
+    }
+
+    #[inline]
+    fn add_with_carry(a: &u128, b: &u128) -> (u128, u128) {
+        let r = a.overflowing_add(*b);
+        (r.0, r.1 as u128)
+    }
+
+    #[inline]
+    fn csub(a: &u128, b: &u128, c: &u128) -> (u128, u128) {
+        let c = (!c).overflowing_add(1).0;
+        let r = a.overflowing_sub(b & c);
+        (r.0, r.1 as u128)
+    }
+
+    #[inline]
+    fn sub_with_borrow(a: &u128, b: &u128) -> (u128, u128) {
+        let r = a.overflowing_sub(*b);
+        (r.0, r.1 as u128)
+    }
+
+    #[inline]
+    fn cmul(a: &u128, b: &u128, c: &u128) -> (u128, u128) {
+        let mask = (!c).overflowing_add(1).0;
+        let (lo, hi) = u128::mul_with_carry(a, b);
+        ((lo & mask) ^ (a & !mask), hi & mask)
+    }
+
+    #[inline]
+    fn mul_with_carry(a: &u128, b: &u128) -> (u128, u128) {
+        // There's no u256, so this is done with four 64x64 bit products.
+        const LOW: u128 = 0xFFFF_FFFF_FFFF_FFFF;
+        let (a0, a1) = (a & LOW, a >> 64);
+        let (b0, b1) = (b & LOW, b >> 64);
+        let p00 = a0 * b0;
+        let p01 = a0 * b1;
+        let p10 = a1 * b0;
+        let p11 = a1 * b1;
+
+        // This can't overflow: 3 * (2^64 - 1) < 2^128
+        let mid = (p00 >> 64) + (p01 & LOW) + (p10 & LOW);
+        let lo = (p00 & LOW) | (mid << 64);
+        let hi = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+        (lo, hi)
+    }
+
+    #[inline]
+    fn cswap(a: &u128, b: &u128, c: &u128) -> (u128, u128) {
+        let c = (!c).overflowing_add(1).0;
+        let mask = (a ^ b) & c;
+        (a ^ mask, b ^ mask)
+    }
+}
+
 impl CTimeOperations<u64> for u64 {
     #[inline]
     fn equal(a: &u64, b: &u64) -> u64 {
@@ -64,6 +231,19 @@ impl CTimeOperations<u64> for u64 {
         (r.0, r.1 as u64)
     }
 
+    #[inline]
+    fn csub(a: &u64, b: &u64, c: &u64) -> (u64, u64) {
+        let c = (!c).overflowing_add(1).0;
+        let r = a.overflowing_sub(b & c);
+        (r.0, r.1 as u64)
+    }
+
+    #[inline]
+    fn sub_with_borrow(a: &u64, b: &u64) -> (u64, u64) {
+        let r = a.overflowing_sub(*b);
+        (r.0, r.1 as u64)
+    }
+
     #[inline]
     fn cmul(a: &u64, b: &u64, c: &u64) -> (u64, u64) {
         let mask = (!u128::from(*c)).overflowing_add(1).0;
@@ -115,6 +295,19 @@ impl CTimeOperations<u32> for u32 {
         (r.0, r.1 as u32)
     }
 
+    #[inline]
+    fn csub(a: &u32, b: &u32, c: &u32) -> (u32, u32) {
+        let c = (!c).overflowing_add(1).0;
+        let r = a.overflowing_sub(b & c);
+        (r.0, r.1 as u32)
+    }
+
+    #[inline]
+    fn sub_with_borrow(a: &u32, b: &u32) -> (u32, u32) {
+        let r = a.overflowing_sub(*b);
+        (r.0, r.1 as u32)
+    }
+
     #[inline]
     fn cmul(a: &u32, b: &u32, c: &u32) -> (u32, u32) {
         let mask = (!u64::from(*c)).overflowing_add(1).0;
@@ -165,6 +358,19 @@ impl CTimeOperations<u16> for u16 {
         (r.0, r.1 as u16)
     }
 
+    #[inline]
+    fn csub(a: &u16, b: &u16, c: &u16) -> (u16, u16) {
+        let c = (!c).overflowing_add(1).0;
+        let r = a.overflowing_sub(b & c);
+        (r.0, r.1 as u16)
+    }
+
+    #[inline]
+    fn sub_with_borrow(a: &u16, b: &u16) -> (u16, u16) {
+        let r = a.overflowing_sub(*b);
+        (r.0, r.1 as u16)
+    }
+
     #[inline]
     fn cmul(a: &u16, b: &u16, c: &u16) -> (u16, u16) {
         let mask = (!u32::from(*c)).overflowing_add(1).0;
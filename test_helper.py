@@ -1,10 +1,33 @@
 import sys
 
-if not len(sys.argv) == 4:
-    print("ERROR; Usage: python test_helper.py op number1 number2")
+if not len(sys.argv) in [4, 5]:
+    print("ERROR; Usage: python test_helper.py op number1 number2 [modulus]")
     exit(1)
 
 if sys.argv[1] == "add":
     print(hex(int(sys.argv[2], 16) + int(sys.argv[3], 16)))
+elif sys.argv[1] == "sub":
+    print(hex(int(sys.argv[2], 16) - int(sys.argv[3], 16)))
+elif sys.argv[1] == "sub_mod":
+    print(hex((int(sys.argv[2], 16) - int(sys.argv[3], 16)) % int(sys.argv[4], 16)))
 elif sys.argv[1] == "mul":
     print(hex(int(sys.argv[2], 16) * int(sys.argv[3], 16)))
+elif sys.argv[1] == "mod":
+    print(hex(int(sys.argv[2], 16) % int(sys.argv[3], 16)))
+elif sys.argv[1] == "add_mod":
+    print(hex((int(sys.argv[2], 16) + int(sys.argv[3], 16)) % int(sys.argv[4], 16)))
+elif sys.argv[1] == "mul_mod":
+    print(hex((int(sys.argv[2], 16) * int(sys.argv[3], 16)) % int(sys.argv[4], 16)))
+elif sys.argv[1] == "pow_mod":
+    print(hex(pow(int(sys.argv[2], 16), int(sys.argv[3], 16), int(sys.argv[4], 16))))
+elif sys.argv[1] == "pow":
+    print(hex(int(sys.argv[2], 16) ** int(sys.argv[3], 16)))
+elif sys.argv[1] == "radix":
+    # Write number1 in base number2 without prefix.
+    x, radix = int(sys.argv[2], 16), int(sys.argv[3], 16)
+    digits = "0123456789abcdefghijklmnopqrstuvwxyz"
+    res = digits[x % radix]
+    while x >= radix:
+        x //= radix
+        res = digits[x % radix] + res
+    print(res)
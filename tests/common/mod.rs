@@ -0,0 +1,52 @@
+//!
+//! Helpers shared by the integration tests. Every test crate only uses some
+//! of them.
+//!
+#![allow(dead_code)]
+
+use rand::{thread_rng, Rng};
+
+use cuint::base::{Uint, UintTrait};
+use cuint::util::Limb;
+
+/// A random hex string with up to `len` digits, e.g. `0x1f`.
+pub fn random_hex_string(len: usize) -> String {
+    const HEX_CHARS: [char; 16] = [
+        '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'a', 'b', 'c', 'd', 'e', 'f',
+    ];
+
+    let mut res = "".to_string();
+    for _ in 0..len {
+        res.push(HEX_CHARS[thread_rng().gen_range(0, HEX_CHARS.len())]);
+    }
+    res = res.trim_start_matches('0').to_string();
+    if res.is_empty() {
+        // Python can't parse "0x".
+        res.push('0');
+    }
+    let mut start = String::from("0x");
+    start.push_str(&res);
+    start
+}
+
+/// Run `test_helper.py` with `args` and return its output.
+pub fn run_test_helper(args: &[&str]) -> String {
+    let expected = std::process::Command::new("python")
+        .arg("test_helper.py")
+        .args(args)
+        .output()
+        .expect("failed to execute python test helper");
+    let expected = String::from_utf8_lossy(&expected.stdout)
+        .replace("\n", "")
+        .replace("\r", "");
+    // Python2 appends an L.
+    expected.replace("L", "")
+}
+
+/// A random Uint<T> with exactly `limbs` limbs.
+pub fn random_uint<T: Limb>(limbs: usize) -> Uint<T> {
+    let bytes: Vec<u8> = (0..T::BITS / 8 * limbs)
+        .map(|_| thread_rng().gen())
+        .collect();
+    Uint::<T>::from_le_bytes(&bytes).unwrap()
+}
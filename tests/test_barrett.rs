@@ -0,0 +1,143 @@
+extern crate cuint;
+extern crate rand;
+
+mod common;
+
+use std::str::FromStr;
+// Trait with all the functions.
+use cuint::barrett::BarrettReducer;
+use cuint::base::{Uint, UintError, UintTrait};
+use cuint::util::Limb;
+
+use common::{random_hex_string, run_test_helper};
+
+#[test]
+fn test_new() {
+    let zero = Uint::<u32>::default();
+    assert_eq!(
+        Err(UintError::DivisionByZero),
+        BarrettReducer::new(&zero).map(|_| ())
+    );
+    // Zero limbs are still zero.
+    let zero = Uint::<u32>::from_be_bytes(&[0; 12]).unwrap();
+    assert_eq!(
+        Err(UintError::DivisionByZero),
+        BarrettReducer::new(&zero).map(|_| ())
+    );
+
+    let m = Uint::<u32>::from_str("0x10000000000000000").unwrap();
+    let reducer = BarrettReducer::new(&m).unwrap();
+    assert_eq!(&m, reducer.modulus());
+}
+
+fn test_core<T: Limb>(a: &str, b: &str, m: &str) {
+    let x = Uint::<T>::from_str(a).unwrap();
+    let y = Uint::<T>::from_str(b).unwrap();
+    let z = Uint::<T>::from_str(m).unwrap();
+    let reducer = BarrettReducer::new(&z).unwrap();
+
+    let c = reducer.reduce(&x);
+    assert_eq!(T::ONE, c.ct_eq(&x.mod_(&z)), "{} % {}", a, m);
+    let c = reducer.reduce(&x.mul_(&y));
+    assert_eq!(
+        T::ONE,
+        c.ct_eq(&x.mul_mod_(&y, &z)),
+        "{} * {} % {}",
+        a,
+        b,
+        m
+    );
+    let c = reducer.mul_mod(&x, &y);
+    assert_eq!(
+        T::ONE,
+        c.ct_eq(&x.mul_mod_(&y, &z)),
+        "{} * {} % {}",
+        a,
+        b,
+        m
+    );
+    assert_eq!(z.bit_width(), c.bit_width());
+}
+
+#[test]
+fn test_reduce() {
+    for i in 1..40 {
+        let m = random_hex_string(4 * i);
+        if m == "0x0" {
+            continue;
+        }
+        for j in 1..(2 * i + 4) {
+            let (a, b) = (random_hex_string(4 * j), random_hex_string(4 * i));
+            test_core::<u16>(&a, &b, &m);
+            test_core::<u32>(&a, &b, &m);
+            test_core::<u64>(&a, &b, &m);
+        }
+    }
+
+    // Even moduli, powers of two and all bits set.
+    for m in [
+        "0x1",
+        "0x2",
+        "0x10000",
+        "0x100000000",
+        "0xffffffffffffffff",
+        "0x8000000000000000000000000000000000",
+        "0xfffffffffffffffffffffffffffffffffffffffe",
+    ] {
+        for _ in 0..10 {
+            let (a, b) = (random_hex_string(80), random_hex_string(40));
+            test_core::<u16>(&a, &b, m);
+            test_core::<u32>(&a, &b, m);
+            test_core::<u64>(&a, &b, m);
+        }
+        let max = format!("0x{}", "f".repeat(2 * (m.len() - 2)));
+        test_core::<u16>(&max, &max, m);
+        test_core::<u32>(&max, &max, m);
+        test_core::<u64>(&max, &max, m);
+    }
+}
+
+#[test]
+fn test_leading_zero_limbs() {
+    // The modulus has two zero limbs at the top.
+    let mut bytes = vec![0u8; 8];
+    bytes.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef, 0xca, 0xfe, 0xba, 0xbe]);
+    let m = Uint::<u32>::from_be_bytes(&bytes).unwrap();
+    let reducer = BarrettReducer::new(&m).unwrap();
+    let x = Uint::<u32>::from_str(&random_hex_string(64)).unwrap();
+    let y = Uint::<u32>::from_str(&random_hex_string(16)).unwrap();
+    assert_eq!(x.mod_(&m), reducer.reduce(&x));
+    assert_eq!(x.mul_mod_(&y, &m), reducer.mul_mod(&x, &y));
+    assert_eq!(
+        run_test_helper(&["pow_mod", &x.to_str(), &y.to_str(), &m.to_str()]),
+        reducer.pow_mod(&x, &y).to_str()
+    );
+    assert_eq!(4, reducer.pow_mod(&x, &y).bit_width() / 32);
+    assert_eq!(reducer.pow_mod(&x, &y), x.pow_mod_(&y, &m));
+
+    // pow_mod_ doesn't skip the zero limbs, the smallest moduli still work.
+    let one = Uint::<u32>::from_be_bytes(&[0, 0, 0, 0, 0, 0, 0, 1]).unwrap();
+    assert_eq!("0x0", x.pow_mod_(&y, &one).to_str());
+    let three = Uint::<u32>::from_be_bytes(&[0, 0, 0, 0, 0, 0, 0, 3]).unwrap();
+    assert_eq!(
+        run_test_helper(&["pow_mod", &x.to_str(), &y.to_str(), "0x3"]),
+        x.pow_mod_(&y, &three).to_str()
+    );
+}
+
+#[test]
+fn test_pow_mod() {
+    for i in 1..10 {
+        let m = random_hex_string(32 * i);
+        if m == "0x0" {
+            continue;
+        }
+        let (a, b) = (random_hex_string(32 * i), random_hex_string(32 * i));
+        let x = Uint::<u64>::from_str(&a).unwrap();
+        let y = Uint::<u64>::from_str(&b).unwrap();
+        let z = Uint::<u64>::from_str(&m).unwrap();
+        let reducer = BarrettReducer::new(&z).unwrap();
+        let c = reducer.pow_mod(&x, &y);
+        assert_eq!(run_test_helper(&["pow_mod", &a, &b, &m]), c.to_str());
+    }
+}
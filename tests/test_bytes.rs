@@ -0,0 +1,114 @@
+extern crate cuint;
+extern crate rand;
+
+use rand::{thread_rng, Rng};
+// Trait with all the functions.
+use cuint::base::{Uint, UintTrait};
+use cuint::fixed::FixedUint;
+
+/// Some u128 values of all byte lengths.
+fn values() -> Vec<u128> {
+    let mut values = vec![0, 1, 0xff, 0x100, u128::MAX];
+    for bytes in 1..=16 {
+        let x: u128 = thread_rng().gen();
+        values.push(x >> (128 - 8 * bytes));
+        values.push(1 << (8 * bytes - 1));
+    }
+    values
+}
+
+/// Number of bytes needed for `x`.
+fn byte_len(x: u128) -> usize {
+    (128 - x.leading_zeros() as usize).div_ceil(8)
+}
+
+/// Check the conversions of `X`, which can hold `max_bytes` bytes.
+fn test_core<X: UintTrait + std::fmt::Debug>(max_bytes: usize) {
+    for x in values() {
+        let be = x.to_be_bytes();
+        let le = x.to_le_bytes();
+        let fits = byte_len(x) <= max_bytes;
+
+        let a = X::from_be_bytes(&be);
+        let b = X::from_le_bytes(&le);
+        assert_eq!(fits, a.is_ok(), "{:#x}", x);
+        assert_eq!(fits, b.is_ok(), "{:#x}", x);
+        if !fits {
+            continue;
+        }
+        let (a, b) = (a.unwrap(), b.unwrap());
+        assert_eq!(format!("{:#x}", x), a.to_str());
+        assert_eq!(a, b);
+
+        // Only the lower bytes of the full output are set.
+        let out_be = a.to_be_bytes();
+        let out_le = a.to_le_bytes();
+        assert_eq!(out_be.len(), out_le.len());
+        let n = std::cmp::min(out_le.len(), 16);
+        assert_eq!(&le[..n], &out_le[..n]);
+        assert!(out_le[n..].iter().all(|&b| b == 0));
+        assert_eq!(out_le.iter().rev().cloned().collect::<Vec<u8>>(), out_be);
+
+        for len in 0..20 {
+            let padded_be = a.to_be_bytes_padded(len);
+            let padded_le = a.to_le_bytes_padded(len);
+            if byte_len(x) > len {
+                assert!(padded_be.is_err());
+                assert!(padded_le.is_err());
+                continue;
+            }
+            let (padded_be, padded_le) = (padded_be.unwrap(), padded_le.unwrap());
+            assert_eq!(len, padded_be.len());
+            assert_eq!(len, padded_le.len());
+            assert_eq!(x, u128::from_be_bytes(pad_be(&padded_be)));
+            assert_eq!(x, u128::from_le_bytes(pad_le(&padded_le)));
+        }
+    }
+}
+
+fn pad_be(bytes: &[u8]) -> [u8; 16] {
+    let mut r = [0u8; 16];
+    let n = std::cmp::min(bytes.len(), 16);
+    r[16 - n..].copy_from_slice(&bytes[bytes.len() - n..]);
+    r
+}
+
+fn pad_le(bytes: &[u8]) -> [u8; 16] {
+    let mut r = [0u8; 16];
+    let n = std::cmp::min(bytes.len(), 16);
+    r[..n].copy_from_slice(&bytes[..n]);
+    r
+}
+
+#[test]
+fn test_uint() {
+    test_core::<Uint<u16>>(usize::MAX);
+    test_core::<Uint<u32>>(usize::MAX);
+    test_core::<Uint<u64>>(usize::MAX);
+}
+
+#[test]
+fn test_fixed() {
+    test_core::<FixedUint<1, u16>>(2);
+    test_core::<FixedUint<3, u16>>(6);
+    test_core::<FixedUint<3, u32>>(12);
+    test_core::<FixedUint<2, u64>>(16);
+    test_core::<FixedUint<4, u64>>(32);
+}
+
+#[test]
+fn test_lengths() {
+    // Uints have as many limbs as needed for all input bytes.
+    let x = Uint::<u32>::from_be_bytes(&[0, 0, 0, 0, 0, 1]).unwrap();
+    assert_eq!(vec![0, 0, 0, 0, 0, 0, 0, 1], x.to_be_bytes());
+    let x = Uint::<u64>::from_le_bytes(&[1]).unwrap();
+    assert_eq!(vec![1, 0, 0, 0, 0, 0, 0, 0], x.to_le_bytes());
+    let x = Uint::<u16>::from_be_bytes(&[]).unwrap();
+    assert_eq!("0x0", x.to_str());
+    assert!(x.to_be_bytes().is_empty());
+
+    // Leading zeros beyond the width of fixed types are fine.
+    let x = FixedUint::<1, u32>::from_be_bytes(&[0, 0, 0, 0, 0xde, 0xad, 0xbe, 0xef]).unwrap();
+    assert_eq!("0xdeadbeef", x.to_str());
+    assert_eq!(vec![0xde, 0xad, 0xbe, 0xef], x.to_be_bytes());
+}
@@ -2,38 +2,29 @@
 extern crate cuint;
 extern crate rand;
 
-use rand::{thread_rng, Rng};
+mod common;
+
 use std::str::FromStr;
 // Trait with all the functions.
 use cuint::base::{Uint, UintTrait};
 
+use common::{random_hex_string, run_test_helper};
+
 #[allow(dead_code)]
-fn random_hex_string(len: usize) -> String {
-    const HEX_CHARS: [char; 16] = [
-        '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'a', 'b', 'c', 'd', 'e', 'f',
-    ];
+fn get_expected(op: &'static str, a: &str, b: &str) -> String {
+    run_test_helper(&[op, a, b])
+}
 
-    let mut res = "".to_string();
-    for _ in 0..len {
-        res.push(HEX_CHARS[thread_rng().gen_range(0, HEX_CHARS.len())]);
-    }
-    res = res.trim_start_matches('0').to_string();
-    let mut start = String::from("0x");
-    start.push_str(&res);
-    start
+#[allow(dead_code)]
+fn get_expected_mod(op: &'static str, a: &str, b: &str, m: &str) -> String {
+    run_test_helper(&[op, a, b, m])
 }
 
+/// Get a random Uint<u32> that is smaller than `m`.
 #[allow(dead_code)]
-fn get_expected(op: &'static str, a: &String, b: &String) -> String {
-    let expected = std::process::Command::new("python")
-        .args(&["test_helper.py", op, &a, &b])
-        .output()
-        .expect("failed to execute python test helper");
-    let expected = String::from_utf8_lossy(&expected.stdout)
-        .replace("\n", "")
-        .replace("\r", "");
-    // Python2 appends an L.
-    expected.replace("L", "")
+fn random_reduced(len: usize, m: &Uint<u32>) -> Uint<u32> {
+    let x = Uint::<u32>::from_str(&random_hex_string(len)).unwrap();
+    &x % m
 }
 
 #[test]
@@ -51,8 +42,236 @@ fn test_encode_decode() {
     }
 }
 
+#[test]
+fn test_encode_chars() {
+    // Every ASCII character and a few others, compared with `to_digit`.
+    let chars = (0u8..0x80).map(char::from).chain("éÿ€".chars());
+    for c in chars {
+        let s = format!("0x1{}", c);
+        let x = Uint::<u32>::from_str(&s);
+        match c.to_digit(16) {
+            Some(d) => assert_eq!(format!("{:#x}", 0x10 + d), x.unwrap().to_str()),
+            None => assert!(x.is_err(), "{:?} should not parse", s),
+        }
+    }
+
+    // Upper case digits are accepted, but written as lower case.
+    let x = Uint::<u32>::from_str("0xABCDEF0123456789").unwrap();
+    assert_eq!("0xabcdef0123456789", x.to_str());
+
+    // Leading zeros are dropped.
+    let x = Uint::<u32>::from_str("0x0000000000000000000abc").unwrap();
+    assert_eq!("0xabc", x.to_str());
+    let x = Uint::<u32>::from_str("0x00000000000000000").unwrap();
+    assert_eq!("0x0", x.to_str());
+
+    assert!(Uint::<u32>::default().encode("123").is_err());
+    assert!(Uint::<u32>::from_str("0x12 34").is_err());
+}
+
+#[test]
+fn test_decode_padded() {
+    let x = Uint::<u32>::from_str("0x123").unwrap();
+    assert_eq!("0x00000123", x.decode_padded().unwrap());
+    let x = Uint::<u32>::from_str("0x0000000000abc").unwrap();
+    assert_eq!("0x0000000000000abc", x.decode_padded().unwrap());
+    let x = Uint::<u32>::from_str("0x0").unwrap();
+    assert_eq!("0x00000000", x.decode_padded().unwrap());
+
+    for i in 1..50 {
+        let s = random_hex_string(i);
+        let x = Uint::<u32>::from_str(&s).unwrap();
+        let padded = x.decode_padded().unwrap();
+        assert_eq!(2 + 8 * (s.len() - 2).div_ceil(8), padded.len());
+        assert_eq!(x, Uint::<u32>::from_str(&padded).unwrap());
+    }
+
+    create_cuint!(CUint32_2, 2, u32);
+    let x = CUint32_2::from_str("0x1").unwrap();
+    assert_eq!("0x0000000000000001", x.decode_padded().unwrap());
+    assert!(CUint32_2::from_str("0x10000000000000000").is_err());
+    assert!(CUint32_2::from_str("0x00000000000000001").is_ok());
+}
+
+#[test]
+fn test_radix() {
+    fn test_radix_core(a: &str) {
+        let x = Uint::<u32>::from_str(a).unwrap();
+        for radix in [2u32, 3, 8, 10, 16, 36] {
+            let expected = get_expected("radix", a, &format!("{:#x}", radix));
+            println!("{:?} in base {:?} = {:?}", a, radix, expected);
+            assert_eq!(expected, x.to_str_radix(radix));
+            let y = Uint::<u32>::from_str_radix(&expected, radix).unwrap();
+            assert_eq!(1, x.ct_eq(&y));
+            let y = Uint::<u32>::from_str_radix(&expected.to_uppercase(), radix).unwrap();
+            assert_eq!(1, x.ct_eq(&y));
+        }
+
+        // Prefixes are detected by `from_str`.
+        let prefixed = [
+            (x.to_str_radix(10), 10),
+            (format!("0x{}", x.to_str_radix(16)), 16),
+            (format!("0o{}", x.to_str_radix(8)), 8),
+            (format!("0b{}", x.to_str_radix(2)), 2),
+        ];
+        for (s, radix) in prefixed.iter() {
+            let y = Uint::<u32>::from_str(s).unwrap();
+            assert_eq!(1, x.ct_eq(&y), "{:?} in base {:?}", s, radix);
+        }
+    }
+
+    test_radix_core("0x0");
+    test_radix_core("0x1");
+    test_radix_core("0xffffffff");
+    test_radix_core("0x100000000");
+    for i in 1..40 {
+        test_radix_core(&random_hex_string(4 * i));
+    }
+
+    // Leading zeros
+    let x = Uint::<u32>::from_str("0000000000000000000042").unwrap();
+    assert_eq!("42", x.to_str_radix(10));
+    assert_eq!("0x2a", x.to_str());
+}
+
+#[test]
+fn test_parse_errors() {
+    use cuint::base::UintError;
+
+    fn err(s: &str) -> UintError {
+        Uint::<u32>::from_str(s).unwrap_err()
+    }
+    assert_eq!(UintError::EmptyString, err(""));
+    assert_eq!(UintError::EmptyString, err("0x"));
+    assert_eq!(UintError::EmptyString, err("0b"));
+
+    // Offsets are relative to the start of the string, including the prefix.
+    assert_eq!(UintError::InvalidDigit(2), err("12a"));
+    assert_eq!(UintError::InvalidDigit(0), err("-12"));
+    assert_eq!(UintError::InvalidDigit(4), err("0b102"));
+    assert_eq!(UintError::InvalidDigit(3), err("0o78"));
+    assert_eq!(UintError::InvalidDigit(3), err("0xfg"));
+    assert_eq!(UintError::InvalidDigit(1), err("0X12"));
+    assert_eq!(UintError::InvalidDigit(5), err("0x123 4 5"));
+    let long = format!("0x{}z{}", "f".repeat(100), "z".repeat(20));
+    assert_eq!(UintError::InvalidDigit(102), err(&long));
+    assert_eq!(
+        Err(UintError::InvalidDigit(0)),
+        Uint::<u32>::from_str_radix("z", 35)
+    );
+    assert!(Uint::<u32>::from_str_radix("z", 36).is_ok());
+
+    let mut x = Uint::<u32>::default();
+    assert_eq!(Err(UintError::MissingPrefix), x.encode("123").map(|_| ()));
+    assert_eq!(
+        Err(UintError::InvalidDigit(4)),
+        x.encode("0x12x").map(|_| ())
+    );
+
+    create_cuint!(CUint32_2, 2, u32);
+    assert!(CUint32_2::from_str("18446744073709551615").is_ok());
+    assert_eq!(
+        Err(UintError::Overflow),
+        CUint32_2::from_str("18446744073709551616")
+    );
+    assert_eq!(
+        Err(UintError::Overflow),
+        CUint32_2::from_str("0x10000000000000000")
+    );
+    assert!(CUint32_2::from_str(&format!("0b{}", "1".repeat(64))).is_ok());
+    assert_eq!(
+        Err(UintError::Overflow),
+        CUint32_2::from_str(&format!("0b{}", "1".repeat(65)))
+    );
+    assert_eq!(
+        Err(UintError::InvalidDigit(5)),
+        CUint32_2::from_str("0o123999")
+    );
+}
+
+#[test]
+fn test_error_display() {
+    use cuint::base::UintError;
+    use std::error::Error;
+
+    let e = Uint::<u32>::from_str("0x12g").unwrap_err();
+    assert_eq!("invalid digit at byte 4", e.to_string());
+    let e: Box<dyn Error> = Box::new(UintError::DivisionByZero);
+    assert_eq!(
+        "attempt to calculate the remainder with a divisor of zero",
+        e.to_string()
+    );
+    assert_eq!("modulus must be odd", UintError::EvenModulus.to_string());
+}
+
+#[test]
+fn test_try_ops() {
+    use cuint::base::UintError;
+
+    let a = Uint::<u32>::from_str("0x123456789abcdef").unwrap();
+    let b = Uint::<u32>::from_str("0xfedcba987654321").unwrap();
+    let m = Uint::<u32>::from_str("0x1000000000000001").unwrap();
+    let zero = Uint::<u32>::from_str("0x0").unwrap();
+    // Zero with more limbs is still zero.
+    let long_zero = Uint::<u32>::from_str("0x0000000000000000").unwrap();
+
+    assert_eq!(Ok(&a % &m), a.try_mod_(&m));
+    assert_eq!(Ok(a.mul_mod_(&b, &m)), a.try_mul_mod_(&b, &m));
+    assert_eq!(Ok(a.pow_mod_(&b, &m)), a.try_pow_mod_(&b, &m));
+    assert_eq!(Ok(a.add_mod_(&b, &m)), a.try_add_mod_(&b, &m));
+    for z in [&zero, &long_zero] {
+        assert_eq!(Err(UintError::DivisionByZero), a.try_mod_(z));
+        assert_eq!(Err(UintError::DivisionByZero), a.try_mul_mod_(&b, z));
+        assert_eq!(Err(UintError::DivisionByZero), a.try_pow_mod_(&b, z));
+        assert_eq!(Err(UintError::NotReduced), a.try_add_mod_(&b, z));
+    }
+    assert_eq!(Err(UintError::NotReduced), m.try_add_mod_(&b, &m));
+    assert_eq!(Err(UintError::NotReduced), a.try_add_mod_(&m, &m));
+
+    create_cuint!(CUint32_4, 4, u32);
+    let a = CUint32_4::from_str("0x123456789abcdef").unwrap();
+    let z = CUint32_4::default();
+    assert_eq!(Err(UintError::DivisionByZero), a.try_mod_(&z));
+    assert_eq!(
+        Ok(a),
+        a.try_mod_(&CUint32_4::from_str("0x1000000000000001").unwrap())
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_radix_too_large() {
+    let _x = Uint::<u32>::from_str_radix("123", 37);
+}
+
+#[test]
+fn test_fmt() {
+    for i in 1..40 {
+        let a = random_hex_string(4 * i);
+        let x = Uint::<u32>::from_str(&a).unwrap();
+        assert_eq!(a, format!("{:#x}", x));
+        assert_eq!(a[2..].to_uppercase(), format!("{:X}", x));
+        assert_eq!(get_expected("radix", &a, "0xa"), format!("{}", x));
+        assert_eq!(get_expected("radix", &a, "0x2"), format!("{:b}", x));
+        assert_eq!(get_expected("radix", &a, "0x8"), format!("{:o}", x));
+
+        let padded = format!("{:#0200x}", x);
+        assert_eq!(200, padded.len());
+        assert_eq!(1, x.ct_eq(&Uint::<u32>::from_str(&padded).unwrap()));
+    }
+
+    let x = Uint::<u32>::from_str("0xdeadbeef").unwrap();
+    assert_eq!(
+        "0x00000000000000000000000000000000000000000000000000000000deadbeef",
+        format!("{:#066x}", x)
+    );
+    assert_eq!("3735928559", x.to_string());
+    assert_eq!("0", Uint::<u32>::default().to_string());
+}
+
 #[test]
 fn test_add() {
+    #[allow(clippy::needless_borrow)]
     fn test_add_core(a: &String, b: &String) {
         let x = Uint::<u32>::from_str(&a).unwrap();
         let y = Uint::<u32>::from_str(&b).unwrap();
@@ -78,6 +297,11 @@ fn test_add() {
     let b = "0x1ffffffff".to_string();
     test_add_core(&a, &b);
 
+    // Carry through a full limb
+    let a = "0xffffffffffffffff".to_string();
+    let b = "0x1".to_string();
+    test_add_core(&a, &b);
+
     // Single limb
     let a = random_hex_string(8);
     let b = random_hex_string(8);
@@ -104,9 +328,122 @@ fn test_add() {
     }
 }
 
+#[test]
+fn test_sub() {
+    fn test_sub_core(a: &str, b: &str) {
+        let x = Uint::<u32>::from_str(a).unwrap();
+        let y = Uint::<u32>::from_str(b).unwrap();
+        let (c, borrow) = x.sub_(&y);
+        assert_eq!(c, &x - &y);
+
+        // The difference wraps around at the length of the longer operand.
+        let limbs = std::cmp::max(a.len() - 2, b.len() - 2).div_ceil(8);
+        let m = format!("0x1{}", "0".repeat(8 * limbs));
+        let expected = get_expected_mod("sub_mod", a, b, &m);
+        let expected_borrow = get_expected("sub", a, b).starts_with('-') as u32;
+        println!("{:?} - {:?} = {:?}", a, b, expected);
+        println!("my result: {:?} (borrow {:?})", c.to_str(), borrow);
+        assert_eq!(expected, c.to_str());
+        assert_eq!(expected_borrow, borrow);
+    }
+
+    // Equal
+    test_sub_core("0xffffffff", "0xffffffff");
+    let a = random_hex_string(40);
+    test_sub_core(&a, &a);
+
+    // Borrow through full limbs
+    test_sub_core("0x10000000000000000", "0x1");
+    test_sub_core("0x1", "0x10000000000000000");
+    test_sub_core("0x0", "0x1");
+
+    // Different lengths
+    test_sub_core(&random_hex_string(8), &random_hex_string(16));
+    test_sub_core(&random_hex_string(16), &random_hex_string(8));
+
+    // Longer
+    for i in 5..50 {
+        test_sub_core(&random_hex_string(i), &random_hex_string(i));
+    }
+}
+
+#[test]
+fn test_ct_cmp() {
+    fn test_ct_cmp_core(a: &str, b: &str) {
+        let x = Uint::<u32>::from_str(a).unwrap();
+        let y = Uint::<u32>::from_str(b).unwrap();
+        let diff = get_expected("sub", a, b);
+        let (lt, eq) = (diff.starts_with('-'), diff == "0x0");
+        println!("{:?} <=> {:?}: {:?}", a, b, diff);
+        assert_eq!(eq as u32, x.ct_eq(&y));
+        assert_eq!(lt as u32, x.ct_lt(&y));
+        assert_eq!((!lt && !eq) as u32, x.ct_gt(&y));
+        assert_eq!(!lt as u32, x.ct_gte(&y));
+    }
+
+    // Equal
+    let a = random_hex_string(40);
+    test_ct_cmp_core(&a, &a);
+
+    // Same value, different number of limbs
+    test_ct_cmp_core("0x1", "0x000000000000000000000001");
+    let a = random_hex_string(40);
+    test_ct_cmp_core(&a, &format!("0x{:0>64}", &a[2..]));
+    test_ct_cmp_core(&format!("0x{:0>64}", &a[2..]), &a);
+
+    // Only the top limb or the lowest bit differs
+    test_ct_cmp_core("0x100000000ffffffff", "0x200000000ffffffff");
+    test_ct_cmp_core("0x1ffffffff00000001", "0x1ffffffff00000000");
+
+    // Different lengths
+    test_ct_cmp_core(&random_hex_string(8), &random_hex_string(16));
+    test_ct_cmp_core(&random_hex_string(16), &random_hex_string(8));
+
+    // Longer
+    for i in 5..50 {
+        test_ct_cmp_core(&random_hex_string(i), &random_hex_string(i));
+    }
+}
+
+#[test]
+fn test_conditional() {
+    fn test_conditional_core(a: &str, b: &str) {
+        let x = Uint::<u32>::from_str(a).unwrap();
+        let y = Uint::<u32>::from_str(b).unwrap();
+
+        // Results are compared by value, lengths may have been padded.
+        let r = Uint::conditional_select(&x, &y, 0);
+        assert_eq!(1, r.ct_eq(&x));
+        let r = Uint::conditional_select(&x, &y, 1);
+        assert_eq!(1, r.ct_eq(&y));
+
+        let (mut u, mut v) = (x.clone(), y.clone());
+        Uint::conditional_swap(&mut u, &mut v, 0);
+        assert_eq!((1, 1), (u.ct_eq(&x), v.ct_eq(&y)));
+        Uint::conditional_swap(&mut u, &mut v, 1);
+        assert_eq!((1, 1), (u.ct_eq(&y), v.ct_eq(&x)));
+
+        let mut u = x.clone();
+        u.conditional_assign(&y, 0);
+        assert_eq!(a, u.to_str());
+        u.conditional_assign(&y, 1);
+        assert_eq!(b, u.to_str());
+    }
+
+    // Same lengths
+    test_conditional_core(&random_hex_string(40), &random_hex_string(40));
+    test_conditional_core("0x0", "0xffffffff");
+
+    // Different lengths are padded
+    test_conditional_core(&random_hex_string(8), &random_hex_string(40));
+    test_conditional_core(&random_hex_string(40), &random_hex_string(8));
+    test_conditional_core("0xffffffffffffffffffffffff", "0x0");
+}
+
 #[test]
 fn test_mul() {
     // TODO: move out and re-use.
+    #[allow(clippy::needless_borrow)]
     fn test_mul_core(a: &String, b: &String) {
         let x = Uint::<u32>::from_str(&a).unwrap();
         let y = Uint::<u32>::from_str(&b).unwrap();
@@ -163,8 +500,345 @@ fn test_mul() {
     }
 }
 
+#[test]
+fn test_mod() {
+    fn test_mod_core(a: &String, b: &String) {
+        let x = Uint::<u32>::from_str(a).unwrap();
+        let y = Uint::<u32>::from_str(b).unwrap();
+        let c = &x % &y;
+        let expected = get_expected("mod", a, b);
+        println!("{:?} % {:?} = {:?}", a, b, expected);
+        println!("my result: {:?}", c.to_str());
+        assert_eq!(expected, c.to_str());
+    }
+
+    // Full limbs
+    let a = "0xffffffffffffffff".to_string();
+    let b = "0xffffffff".to_string();
+    test_mod_core(&a, &b);
+
+    // Modulus is 1
+    let a = random_hex_string(40);
+    let b = "0x1".to_string();
+    test_mod_core(&a, &b);
+
+    // Equal
+    let a = random_hex_string(40);
+    test_mod_core(&a, &a);
+
+    // Modulus longer than the dividend
+    let a = random_hex_string(8);
+    let b = random_hex_string(24);
+    test_mod_core(&a, &b);
+    let a = random_hex_string(17);
+    let b = random_hex_string(40);
+    test_mod_core(&a, &b);
+
+    // Zero dividend
+    let a = "0x0".to_string();
+    let b = random_hex_string(16);
+    test_mod_core(&a, &b);
+
+    // Longer
+    for i in 1..50 {
+        let a = random_hex_string(2 * i + 7);
+        let b = random_hex_string(i);
+        if b == "0x0" {
+            // Don't divide by zero.
+            continue;
+        }
+        test_mod_core(&a, &b);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_mod_zero() {
+    let a = Uint::<u32>::from_str(&random_hex_string(32)).unwrap();
+    let b = Uint::<u32>::from_str("0x0").unwrap();
+    let _c = &a % &b;
+}
+
+#[test]
+fn test_add_mod() {
+    fn test_add_mod_core(x: &Uint<u32>, y: &Uint<u32>, m: &Uint<u32>) {
+        let c = x.add_mod_(y, m);
+        let (a, b, m) = (x.to_str(), y.to_str(), m.to_str());
+        let expected = get_expected_mod("add_mod", &a, &b, &m);
+        println!("{:?} + {:?} mod {:?} = {:?}", a, b, m, expected);
+        println!("my result: {:?}", c.to_str());
+        assert_eq!(expected, c.to_str());
+    }
+
+    // Full limb, a + b overflows
+    let m = Uint::<u32>::from_str("0xffffffff").unwrap();
+    let a = Uint::<u32>::from_str("0xfffffffe").unwrap();
+    test_add_mod_core(&a, &a, &m);
+
+    // a + b == m
+    let m = Uint::<u32>::from_str("0x100000000").unwrap();
+    let a = Uint::<u32>::from_str("0x1").unwrap();
+    let b = Uint::<u32>::from_str("0xffffffff").unwrap();
+    test_add_mod_core(&a, &b, &m);
+
+    // Operands shorter than the modulus
+    let m = Uint::<u32>::from_str(&random_hex_string(40)).unwrap();
+    let a = random_reduced(8, &m);
+    let b = random_reduced(16, &m);
+    test_add_mod_core(&a, &b, &m);
+
+    // Longer
+    for i in 1..50 {
+        let m = random_hex_string(i);
+        if m == "0x0" {
+            continue;
+        }
+        let m = Uint::<u32>::from_str(&m).unwrap();
+        let a = random_reduced(i + 3, &m);
+        let b = random_reduced(i + 3, &m);
+        test_add_mod_core(&a, &b, &m);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_add_mod_not_reduced() {
+    let m = Uint::<u32>::from_str("0x123456789").unwrap();
+    let a = Uint::<u32>::from_str("0x123456789").unwrap();
+    let b = Uint::<u32>::from_str("0x1").unwrap();
+    let _c = a.add_mod_(&b, &m);
+}
+
+#[test]
+fn test_mul_mod() {
+    fn test_mul_mod_core(a: &str, b: &str, m: &str) {
+        let x = Uint::<u32>::from_str(a).unwrap();
+        let y = Uint::<u32>::from_str(b).unwrap();
+        let z = Uint::<u32>::from_str(m).unwrap();
+        let c = x.mul_mod_(&y, &z);
+        let expected = get_expected_mod("mul_mod", a, b, m);
+        println!("{:?} * {:?} mod {:?} = {:?}", a, b, m, expected);
+        println!("my result: {:?}", c.to_str());
+        assert_eq!(expected, c.to_str());
+    }
+
+    // Full limbs
+    test_mul_mod_core("0xffffffff", "0xffffffff", "0xfffffffe");
+    test_mul_mod_core(
+        "0xffffffffffffffff",
+        "0xffffffffffffffff",
+        "0xfffffffffffffffe",
+    );
+
+    // Single limb modulus
+    for _ in 0..10 {
+        let m = random_hex_string(8);
+        if m == "0x0" {
+            continue;
+        }
+        test_mul_mod_core(&random_hex_string(8), &random_hex_string(8), &m);
+        test_mul_mod_core(&random_hex_string(40), &random_hex_string(24), &m);
+    }
+
+    // Modulus longer than the product
+    let a = random_hex_string(8);
+    let b = random_hex_string(8);
+    let m = random_hex_string(40);
+    test_mul_mod_core(&a, &b, &m);
+
+    // Longer
+    for i in 1..50 {
+        let m = random_hex_string(i);
+        if m == "0x0" {
+            continue;
+        }
+        test_mul_mod_core(&random_hex_string(i), &random_hex_string(i + 5), &m);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_mul_mod_zero() {
+    let a = Uint::<u32>::from_str(&random_hex_string(32)).unwrap();
+    let b = Uint::<u32>::from_str(&random_hex_string(32)).unwrap();
+    let m = Uint::<u32>::from_str("0x0").unwrap();
+    let _c = a.mul_mod_(&b, &m);
+}
+
+#[test]
+fn test_pow() {
+    fn test_pow_core(a: &str, b: u64) {
+        let x = Uint::<u32>::from_str(a).unwrap();
+        let c = x.pow(b);
+        let expected = get_expected("pow", a, &format!("{:#x}", b));
+        println!("{:?} ^ {:?} = {:?}", a, b, expected);
+        println!("my result: {:?}", c.to_str());
+        assert_eq!(expected, c.to_str());
+    }
+
+    // Powers of 2
+    for k in 0..70 {
+        test_pow_core("0x2", k);
+    }
+
+    // Trivial bases
+    test_pow_core("0x1", 12345);
+    test_pow_core("0x0", 17);
+
+    // Full limbs
+    test_pow_core("0xffffffff", 3);
+    test_pow_core("0xffffffffffffffff", 17);
+
+    // Longer
+    for i in 1..20 {
+        test_pow_core(&random_hex_string(4 * i), i as u64);
+    }
+}
+
+#[test]
+fn test_pow_mod() {
+    fn test_pow_mod_core(a: &str, b: &str, m: &str) {
+        let x = Uint::<u32>::from_str(a).unwrap();
+        let y = Uint::<u32>::from_str(b).unwrap();
+        let z = Uint::<u32>::from_str(m).unwrap();
+        let c = x.pow_mod_(&y, &z);
+        let expected = get_expected_mod("pow_mod", a, b, m);
+        println!("{:?} ^ {:?} mod {:?} = {:?}", a, b, m, expected);
+        println!("my result: {:?}", c.to_str());
+        assert_eq!(expected, c.to_str());
+
+        // All window sizes have to give the same result.
+        for window in 1..7 {
+            assert_eq!(c, x.pow_mod_window_(&y, &z, window));
+        }
+    }
+
+    // Zero exponent
+    test_pow_mod_core("0x1234", "0x0", "0x10001");
+    test_pow_mod_core("0x0", "0x0", "0x10001");
+
+    // Modulus is 1
+    test_pow_mod_core("0x1234", "0x5678", "0x1");
+
+    // Full limbs
+    test_pow_mod_core("0xffffffff", "0xffffffff", "0xfffffffb");
+    test_pow_mod_core(
+        "0xffffffffffffffff",
+        "0xffffffffffffffff",
+        "0xffffffffffffffc5",
+    );
+
+    // Single limb modulus
+    let m = random_hex_string(8);
+    if m != "0x0" {
+        test_pow_mod_core(&random_hex_string(20), &random_hex_string(16), &m);
+    }
+
+    // Longer
+    for i in 1..12 {
+        let m = random_hex_string(8 * i);
+        if m == "0x0" {
+            continue;
+        }
+        test_pow_mod_core(&random_hex_string(8 * i), &random_hex_string(8 * i), &m);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_pow_mod_window_too_large() {
+    let a = Uint::<u32>::from_str("0x1234").unwrap();
+    let _c = a.pow_mod_window_(&a, &a, 9);
+}
+
 #[test]
 fn test_cuint32() {
     create_cuint!(CUint32_256, 256, u32);
-    let cuint32_256 = CUint32_256::default();
+    let zero = CUint32_256::default();
+    assert_eq!("0x0", zero.to_str());
+
+    fn test_core(a: &str, b: &str) {
+        let x = CUint32_256::from_str(a).unwrap();
+        let y = CUint32_256::from_str(b).unwrap();
+        assert_eq!(a, x.to_str());
+
+        let c = x + y;
+        assert_eq!(get_expected("add", a, b), c.to_str());
+        let c = x * y;
+        assert_eq!(get_expected("mul", a, b), c.to_str());
+        let c = x % y;
+        assert_eq!(get_expected("mod", a, b), c.to_str());
+    }
+
+    // Full limbs
+    test_core("0xffffffff", "0xffffffff");
+    test_core("0xffffffffffffffff", "0x1");
+
+    // Longer
+    for i in 1..20 {
+        let b = random_hex_string(20 * i);
+        if b == "0x0" {
+            continue;
+        }
+        test_core(&random_hex_string(50 * i), &b);
+    }
+
+    // Too large
+    assert!(CUint32_256::from_str(&format!("0x1{}", "0".repeat(2048))).is_err());
+}
+
+#[test]
+fn test_cuint32_wrapping() {
+    create_cuint!(CUint32_2, 2, u32);
+    let max = CUint32_2::from_str("0xffffffffffffffff").unwrap();
+    let one = CUint32_2::from_str("0x1").unwrap();
+    let two = CUint32_2::from_str("0x2").unwrap();
+    assert!(CUint32_2::from_str("0x10000000000000000").is_err());
+    // Leading zeroes beyond the width are fine.
+    assert_eq!(one, CUint32_2::from_str("0x000000000000000001").unwrap());
+
+    assert_eq!("0x0", (max + one).to_str());
+    assert_eq!("0x1", (max + two).to_str());
+    assert_eq!("0xfffffffffffffffe", (max * two).to_str());
+    assert_eq!("0x1", (max * max).to_str());
+    assert_eq!("0x0", two.pow(64).to_str());
+    assert_eq!("0x8000000000000000", two.pow(63).to_str());
+    assert_eq!("0x1", two.pow(0).to_str());
+    assert_eq!("0xfffffffffffffffe", (max + max).to_str());
+}
+
+#[test]
+fn test_cuint32_mod() {
+    create_cuint!(CUint32_4, 4, u32);
+
+    fn test_core(a: &str, b: &str, m: &str) {
+        let x = CUint32_4::from_str(a).unwrap();
+        let y = CUint32_4::from_str(b).unwrap();
+        let z = CUint32_4::from_str(m).unwrap();
+
+        let c = x.mul_mod_(&y, &z);
+        assert_eq!(get_expected_mod("mul_mod", a, b, m), c.to_str());
+        let c = x.pow_mod_(&y, &z);
+        assert_eq!(get_expected_mod("pow_mod", a, b, m), c.to_str());
+
+        let x = x % z;
+        let y = y % z;
+        let c = x.add_mod_(&y, &z);
+        let expected = get_expected_mod("add_mod", &x.to_str(), &y.to_str(), m);
+        assert_eq!(expected, c.to_str());
+    }
+
+    test_core("0xffffffff", "0xffffffff", "0xfffffffb");
+    test_core(
+        "0xffffffffffffffffffffffffffffffff",
+        "0xffffffffffffffffffffffffffffffff",
+        "0xffffffffffffffffffffffffffffff61",
+    );
+    for i in 1..32 {
+        let m = random_hex_string(i);
+        if m == "0x0" {
+            continue;
+        }
+        test_core(&random_hex_string(32), &random_hex_string(i), &m);
+    }
 }
@@ -0,0 +1,105 @@
+extern crate cuint;
+extern crate rand;
+
+mod common;
+
+use std::str::FromStr;
+// Trait with all the functions.
+use cuint::base::{Uint, UintTrait};
+
+use common::{random_hex_string, run_test_helper};
+
+#[test]
+fn test_encode_decode() {
+    fn enc_dec(s: &str) {
+        let x = Uint::<u64>::from_str(s).unwrap();
+        let s_dec = x.to_str();
+        println!("{:?} := {:x?} => {:?}", s, x, s_dec);
+        assert_eq!(s, s_dec);
+    }
+    enc_dec("0x0");
+    enc_dec("0x123456789abcdef");
+    enc_dec("0x123456789abcdef0fedcba9876543210");
+    for i in 0..100 {
+        enc_dec(&random_hex_string(128 + 3 * i));
+    }
+}
+
+#[test]
+fn test_ops() {
+    fn test_core(a: &str, b: &str, m: &str) {
+        let x = Uint::<u64>::from_str(a).unwrap();
+        let y = Uint::<u64>::from_str(b).unwrap();
+        let z = Uint::<u64>::from_str(m).unwrap();
+        println!("a: {:?}, b: {:?}, m: {:?}", a, b, m);
+
+        assert_eq!(run_test_helper(&["add", a, b]), (&x + &y).to_str());
+        assert_eq!(run_test_helper(&["mul", a, b]), (&x * &y).to_str());
+        assert_eq!(run_test_helper(&["mod", a, m]), (&x % &z).to_str());
+        let c = x.mul_mod_(&y, &z);
+        assert_eq!(run_test_helper(&["mul_mod", a, b, m]), c.to_str());
+        let c = x.pow_mod_(&y, &z);
+        assert_eq!(run_test_helper(&["pow_mod", a, b, m]), c.to_str());
+
+        let xr = &x % &z;
+        let yr = &y % &z;
+        let c = xr.add_mod_(&yr, &z);
+        let expected = run_test_helper(&["add_mod", &xr.to_str(), &yr.to_str(), m]);
+        assert_eq!(expected, c.to_str());
+    }
+
+    // Full limbs
+    test_core(
+        "0xffffffffffffffff",
+        "0xffffffffffffffff",
+        "0xffffffffffffffc5",
+    );
+    test_core(
+        "0xffffffffffffffffffffffffffffffff",
+        "0x1",
+        "0xffffffffffffffffffffffffffffff61",
+    );
+
+    // Longer
+    for i in 1..40 {
+        let m = random_hex_string(2 * i);
+        if m == "0x0" {
+            continue;
+        }
+        test_core(&random_hex_string(3 * i), &random_hex_string(2 * i), &m);
+    }
+}
+
+#[test]
+fn test_pow() {
+    let two = Uint::<u64>::from_str("0x2").unwrap();
+    for k in 0..200 {
+        let expected = run_test_helper(&["pow", "0x2", &format!("{:#x}", k)]);
+        assert_eq!(expected, two.pow(k).to_str());
+    }
+}
+
+#[test]
+fn test_convert() {
+    fn convert(s: &str) {
+        let x = Uint::<u32>::from_str(s).unwrap();
+        let y = Uint::<u64>::from(&x);
+        assert_eq!(s, y.to_str());
+        let z = Uint::<u32>::from(y);
+        assert_eq!(s, z.to_str());
+        assert_eq!(
+            x.mul_(&x).to_str(),
+            Uint::<u64>::from(x.clone())
+                .mul_(&Uint::<u64>::from(x))
+                .to_str()
+        );
+    }
+
+    convert("0x0");
+    convert("0xffffffff");
+    convert("0x1ffffffff");
+    convert("0xffffffffffffffff");
+    for i in 1..100 {
+        convert(&random_hex_string(i));
+    }
+}
@@ -0,0 +1,157 @@
+extern crate cuint;
+extern crate rand;
+
+mod common;
+
+use std::str::FromStr;
+// Trait with all the functions.
+use cuint::base::{Uint, UintTrait};
+use cuint::fixed::FixedUint;
+
+use common::random_hex_string;
+
+/// A generic function that works for any width.
+fn mul_add_mod<const N: usize>(
+    a: FixedUint<N, u32>,
+    b: FixedUint<N, u32>,
+    m: &FixedUint<N, u32>,
+) -> FixedUint<N, u32> {
+    let ab = a.mul_mod_(&b, m);
+    ab.add_mod_(&(a % m), m)
+}
+
+#[test]
+fn test_generic() {
+    fn test_core<const N: usize>(a: &str, b: &str, m: &str) {
+        let x = FixedUint::<N, u32>::from_str(a).unwrap();
+        let y = FixedUint::<N, u32>::from_str(b).unwrap();
+        let z = FixedUint::<N, u32>::from_str(m).unwrap();
+        let r = mul_add_mod(x, y, &z);
+
+        // Compare with Uint<u32>.
+        let x = Uint::<u32>::from_str(a).unwrap();
+        let y = Uint::<u32>::from_str(b).unwrap();
+        let z = Uint::<u32>::from_str(m).unwrap();
+        let expected = x.mul_mod_(&y, &z).add_mod_(&(&x % &z), &z);
+        assert_eq!(expected.to_str(), r.to_str());
+    }
+
+    // The moduli get a trailing 1 digit, so they are never zero.
+    for _ in 0..10 {
+        test_core::<1>(
+            &random_hex_string(8),
+            &random_hex_string(8),
+            &format!("{}1", random_hex_string(3)),
+        );
+        test_core::<4>(
+            &random_hex_string(32),
+            &random_hex_string(32),
+            &format!("{}1", random_hex_string(19)),
+        );
+        test_core::<9>(
+            &random_hex_string(70),
+            &random_hex_string(72),
+            &format!("{}1", random_hex_string(71)),
+        );
+    }
+}
+
+#[test]
+fn test_copy() {
+    let a = FixedUint::<2, u32>::from_str("0x123456789").unwrap();
+    let b = a;
+    let c = a + b;
+    assert_eq!("0x2468acf12", c.to_str());
+    assert_eq!("0x123456789", a.to_str());
+}
+
+#[test]
+fn test_conditional() {
+    for _ in 0..10 {
+        let a = FixedUint::<4, u64>::from_str(&random_hex_string(60)).unwrap();
+        let b = FixedUint::<4, u64>::from_str(&random_hex_string(20)).unwrap();
+
+        assert_eq!(a, FixedUint::conditional_select(&a, &b, 0));
+        assert_eq!(b, FixedUint::conditional_select(&a, &b, 1));
+
+        let (mut x, mut y) = (a, b);
+        FixedUint::conditional_swap(&mut x, &mut y, 0);
+        assert_eq!((a, b), (x, y));
+        FixedUint::conditional_swap(&mut x, &mut y, 1);
+        assert_eq!((b, a), (x, y));
+
+        let mut x = a;
+        x.conditional_assign(&b, 0);
+        assert_eq!(a, x);
+        x.conditional_assign(&b, 1);
+        assert_eq!(b, x);
+    }
+}
+
+#[test]
+fn test_resize_truncate() {
+    let a = FixedUint::<2, u32>::from_str("0xffffffffffffffff").unwrap();
+    let one = FixedUint::<2, u32>::from_str("0x1").unwrap();
+
+    let wide: FixedUint<3, u32> = a.resize();
+    assert_eq!("0xffffffffffffffff", wide.to_str());
+    let wide = wide + one.resize();
+    assert_eq!("0x10000000000000000", wide.to_str());
+
+    let same: FixedUint<2, u32> = a.resize();
+    assert_eq!(a, same);
+
+    let narrow: FixedUint<1, u32> = wide.truncate();
+    assert_eq!("0x0", narrow.to_str());
+    let narrow: FixedUint<2, u32> = wide.truncate();
+    assert_eq!("0x0", narrow.to_str());
+    let narrow: FixedUint<1, u32> = a.truncate();
+    assert_eq!("0xffffffff", narrow.to_str());
+}
+
+#[test]
+fn test_widening_mul() {
+    let a = FixedUint::<2, u32>::from_str("0xffffffffffffffff").unwrap();
+    let w: FixedUint<4, u32> = a.widening_mul(&a);
+    assert_eq!("0xfffffffffffffffe0000000000000001", w.to_str());
+    assert_eq!("0x1", (a * a).to_str());
+    let low: FixedUint<2, u32> = w.truncate();
+    assert_eq!(a * a, low);
+
+    for _ in 0..10 {
+        let x = random_hex_string(64);
+        let y = random_hex_string(60);
+        let a = FixedUint::<8, u32>::from_str(&x).unwrap();
+        let b = FixedUint::<8, u32>::from_str(&y).unwrap();
+        let w: FixedUint<16, u32> = a.widening_mul(&b);
+        let expected = &Uint::<u32>::from_str(&x).unwrap() * &Uint::<u32>::from_str(&y).unwrap();
+        assert_eq!(expected.to_str(), w.to_str());
+    }
+}
+
+#[test]
+fn test_limb_types() {
+    for _ in 0..10 {
+        let x = random_hex_string(64);
+        let y = random_hex_string(60);
+        // A trailing 1 digit, so the modulus is never zero.
+        let m = format!("{}1", random_hex_string(39));
+        let expected = Uint::<u32>::from_str(&x)
+            .unwrap()
+            .mul_mod_(
+                &Uint::<u32>::from_str(&y).unwrap(),
+                &Uint::<u32>::from_str(&m).unwrap(),
+            )
+            .to_str();
+
+        let a = FixedUint::<16, u16>::from_str(&x).unwrap();
+        let b = FixedUint::<16, u16>::from_str(&y).unwrap();
+        let c = FixedUint::<16, u16>::from_str(&m).unwrap();
+        assert_eq!(expected, a.mul_mod_(&b, &c).to_str());
+
+        let a = FixedUint::<4, u64>::from_str(&x).unwrap();
+        let b = FixedUint::<4, u64>::from_str(&y).unwrap();
+        let c = FixedUint::<4, u64>::from_str(&m).unwrap();
+        assert_eq!(expected, a.mul_mod_(&b, &c).to_str());
+    }
+}
@@ -0,0 +1,71 @@
+extern crate cuint;
+extern crate rand;
+
+mod common;
+
+// Trait with all the functions.
+use cuint::base::{Uint, UintTrait};
+use cuint::uint::KARATSUBA_THRESHOLD;
+
+use common::{random_uint, run_test_helper};
+
+/// The schoolbook product: `mul_karatsuba_` with a `usize::MAX` threshold
+/// never splits and falls through to `mul_slice`.
+fn schoolbook(a: &Uint<u32>, b: &Uint<u32>) -> Uint<u32> {
+    a.mul_karatsuba_(b, usize::MAX)
+}
+
+#[test]
+fn test_all_lengths() {
+    for len in 1..=256 {
+        let a = random_uint(len);
+        let b = random_uint(len);
+        let expected = schoolbook(&a, &b);
+        for threshold in [4, 5, 8, KARATSUBA_THRESHOLD] {
+            assert_eq!(expected, a.mul_karatsuba_(&b, threshold), "{}", len);
+        }
+        assert_eq!(expected, a.mul_(&b), "{}", len);
+    }
+}
+
+#[test]
+fn test_unbalanced() {
+    for (len_a, len_b) in [
+        (1, 256),
+        (3, 100),
+        (17, 64),
+        (33, 65),
+        (100, 255),
+        (128, 129),
+    ] {
+        let a = random_uint(len_a);
+        let b = random_uint(len_b);
+        let expected = schoolbook(&a, &b);
+        for threshold in [4, 7, KARATSUBA_THRESHOLD] {
+            assert_eq!(expected, a.mul_karatsuba_(&b, threshold));
+            assert_eq!(expected, b.mul_karatsuba_(&a, threshold));
+        }
+    }
+}
+
+#[test]
+fn test_extreme_values() {
+    // All limbs set, such that every sum has a carry.
+    for len in [2, 7, 64, 65, 200] {
+        let max = Uint::<u32>::from_le_bytes(&vec![0xff; 4 * len]).unwrap();
+        let expected = run_test_helper(&["mul", &max.to_str(), &max.to_str()]);
+        assert_eq!(expected, max.mul_karatsuba_(&max, 4).to_str());
+        assert_eq!(expected, max.mul_(&max).to_str());
+    }
+
+    let zero = Uint::<u32>::from_le_bytes(&[0; 400]).unwrap();
+    let a = random_uint(100);
+    assert_eq!("0x0", a.mul_karatsuba_(&zero, 4).to_str());
+    assert_eq!(200, a.mul_karatsuba_(&zero, 4).bit_width() / 32);
+}
+
+#[test]
+#[should_panic(expected = "at least 4")]
+fn test_threshold() {
+    random_uint::<u32>(4).mul_karatsuba_(&random_uint(4), 3);
+}
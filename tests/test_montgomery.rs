@@ -0,0 +1,119 @@
+extern crate cuint;
+extern crate rand;
+
+mod common;
+
+use std::str::FromStr;
+// Trait with all the functions.
+use cuint::base::{Uint, UintError, UintTrait};
+use cuint::montgomery::MontgomeryParams;
+use cuint::util::Limb;
+
+use common::{random_hex_string, run_test_helper};
+
+/// A random odd hex string with up to `len` digits.
+fn random_odd_hex_string(len: usize) -> String {
+    let mut s = random_hex_string(len);
+    let last = s.pop().unwrap().to_digit(16).unwrap() | 1;
+    s.push(std::char::from_digit(last, 16).unwrap());
+    s
+}
+
+#[test]
+fn test_params() {
+    for m in ["0x0", "0x2", "0x10000000000000000", "0xfffffffffffffffe"] {
+        let m = Uint::<u32>::from_str(m).unwrap();
+        assert_eq!(
+            Err(UintError::EvenModulus),
+            MontgomeryParams::new(&m).map(|_| ())
+        );
+    }
+
+    let m = Uint::<u32>::from_str("0xfffffffb").unwrap();
+    let params = MontgomeryParams::new(&m).unwrap();
+    assert_eq!(&m, params.modulus());
+    // R = 2^32, so R mod m = 5.
+    assert_eq!("0x5", params.one().to_str());
+    let one = Uint::<u32>::from_str("0x1").unwrap();
+    assert_eq!(params.one(), params.to_montgomery(&one));
+    assert_eq!(one, params.from_montgomery(&params.one()));
+}
+
+fn test_core<T: Limb>(a: &str, b: &str, m: &str) {
+    let x = Uint::<T>::from_str(a).unwrap();
+    let y = Uint::<T>::from_str(b).unwrap();
+    let z = Uint::<T>::from_str(m).unwrap();
+    let params = MontgomeryParams::new(&z).unwrap();
+
+    let xm = params.to_montgomery(&x);
+    let ym = params.to_montgomery(&y);
+    assert_eq!(T::ONE, params.from_montgomery(&xm).ct_eq(&x.mod_(&z)));
+
+    let c = params.from_montgomery(&params.mont_mul(&xm, &ym));
+    assert_eq!(
+        T::ONE,
+        c.ct_eq(&x.mul_mod_(&y, &z)),
+        "{} * {} % {}",
+        a,
+        b,
+        m
+    );
+    let c = params.mont_sqr(&xm);
+    assert_eq!(T::ONE, c.ct_eq(&params.mont_mul(&xm, &xm)));
+
+    let c = params.pow_mod(&x, &y);
+    assert_eq!(
+        T::ONE,
+        c.ct_eq(&x.pow_mod_(&y, &z)),
+        "{} ^ {} % {}",
+        a,
+        b,
+        m
+    );
+}
+
+#[test]
+fn test_ops() {
+    for i in 1..40 {
+        let m = random_odd_hex_string(4 * i);
+        let (a, b) = (random_hex_string(8 * i), random_hex_string(4 * i));
+        test_core::<u16>(&a, &b, &m);
+        test_core::<u32>(&a, &b, &m);
+        test_core::<u64>(&a, &b, &m);
+    }
+
+    // Small moduli and moduli with all bits set.
+    for m in [
+        "0xffffffffffffffffffffffffffffffff",
+        "0x1",
+        "0x3",
+        "0x10001",
+    ] {
+        let (a, b) = (random_hex_string(40), random_hex_string(40));
+        test_core::<u16>(&a, &b, m);
+        test_core::<u32>(&a, &b, m);
+        test_core::<u64>(&a, &b, m);
+    }
+}
+
+#[test]
+fn test_pow_mod() {
+    for i in 1..10 {
+        let m = random_odd_hex_string(32 * i);
+        let (a, b) = (random_hex_string(32 * i), random_hex_string(32 * i));
+        let x = Uint::<u64>::from_str(&a).unwrap();
+        let y = Uint::<u64>::from_str(&b).unwrap();
+        let z = Uint::<u64>::from_str(&m).unwrap();
+        let params = MontgomeryParams::new(&z).unwrap();
+        let c = params.pow_mod(&x, &y);
+        assert_eq!(run_test_helper(&["pow_mod", &a, &b, &m]), c.to_str());
+    }
+}
+
+#[test]
+#[should_panic(expected = "not reduced")]
+fn test_not_reduced() {
+    let m = Uint::<u32>::from_str("0xfffffffb").unwrap();
+    let params = MontgomeryParams::new(&m).unwrap();
+    params.mont_mul(&m, &m);
+}
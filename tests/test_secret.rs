@@ -0,0 +1,99 @@
+extern crate cuint;
+
+use std::str::FromStr;
+// Trait with all the functions.
+use cuint::base::{Uint, UintError, UintTrait};
+use cuint::fixed::FixedUint;
+use cuint::secret::SecretUint;
+
+const KEY: &str = "0xdeadbeefcafebabe0123456789abcdef";
+
+#[test]
+fn test_redacted() {
+    let key = SecretUint::new(Uint::<u32>::from_str(KEY).unwrap());
+    let printed = [
+        format!("{:?}", key),
+        format!("{:x?}", key),
+        format!("{:#?}", key),
+        format!("{}", key),
+    ];
+    for s in printed.iter() {
+        println!("{}", s);
+        assert!(s.contains("128"));
+        for limb in ["deadbeef", "cafebabe", "3735928559", "dead"] {
+            assert!(!s.contains(limb), "{:?} leaks the value", s);
+        }
+    }
+    assert_eq!("<secret: 128 bits>", key.to_string());
+    assert_eq!("SecretUint { bits: 128, .. }", format!("{:?}", key));
+
+    let key = SecretUint::new(FixedUint::<4, u64>::from_str(KEY).unwrap());
+    assert_eq!("<secret: 256 bits>", key.to_string());
+
+    // Panic messages don't leak the value either.
+    let r = std::panic::catch_unwind(|| {
+        let key = SecretUint::new(Uint::<u32>::from_str(KEY).unwrap());
+        panic!("failed with {:?}", key);
+    });
+    let msg = r.unwrap_err().downcast::<String>().unwrap();
+    assert!(!msg.contains("deadbeef"));
+}
+
+#[test]
+fn test_expose() {
+    let x = Uint::<u32>::from_str(KEY).unwrap();
+    let key = SecretUint::from(x.clone());
+    assert_eq!(&x, key.expose_secret());
+    assert_eq!(KEY, key.expose_secret().to_str());
+}
+
+#[test]
+fn test_arithmetic() {
+    let (a, b, m) = (
+        "0x123456789abcdef0123",
+        "0xfedcba98765432",
+        "0x1000000000000000f",
+    );
+    let x = Uint::<u32>::from_str(a).unwrap();
+    let y = Uint::<u32>::from_str(b).unwrap();
+    let z = Uint::<u32>::from_str(m).unwrap();
+    let (sx, sy, sz) = (
+        SecretUint::new(x.clone()),
+        SecretUint::new(y.clone()),
+        SecretUint::new(z.clone()),
+    );
+
+    assert_eq!(&x + &y, *(&sx + &sy).expose_secret());
+    assert_eq!(&x - &y, *(&sx - &sy).expose_secret());
+    assert_eq!(&x * &y, *(&sx * &sy).expose_secret());
+    assert_eq!(&x % &z, *(&sx % &sz).expose_secret());
+    assert_eq!(x.pow(3), *sx.pow(3).expose_secret());
+    assert_eq!(x.pow_mod_(&y, &z), *sx.pow_mod_(&sy, &sz).expose_secret());
+    assert_eq!(x.mul_mod_(&y, &z), *sx.mul_mod_(&sy, &sz).expose_secret());
+    let (xr, yr) = (&x % &z, &y % &z);
+    let (sxr, syr) = (&sx % &sz, &sy % &sz);
+    assert_eq!(
+        xr.add_mod_(&yr, &z),
+        *sxr.add_mod_(&syr, &sz).expose_secret()
+    );
+
+    let (d, borrow) = sy.sub_(&sx);
+    assert_eq!(1, borrow);
+    assert_eq!(y.sub_(&x).0, *d.expose_secret());
+
+    assert_eq!(1, sx.ct_gt(&sy));
+    assert_eq!(0, sx.ct_lt(&sy));
+    assert_eq!(1, sx.ct_eq(&sx.clone()));
+    let s = SecretUint::conditional_select(&sx, &sy, 1);
+    assert_eq!(1, s.ct_eq(&sy));
+
+    let zero = SecretUint::new(Uint::<u32>::default());
+    assert_eq!(
+        Err(UintError::DivisionByZero),
+        sx.try_mod_(&zero).map(|_| ())
+    );
+    assert_eq!(
+        Err(UintError::DivisionByZero),
+        sx.try_mul_mod_(&sy, &zero).map(|_| ())
+    );
+}
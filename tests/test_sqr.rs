@@ -0,0 +1,77 @@
+extern crate cuint;
+extern crate rand;
+
+mod common;
+
+use std::str::FromStr;
+// Trait with all the functions.
+use cuint::base::{Uint, UintTrait};
+use cuint::fixed::FixedUint;
+use cuint::montgomery::MontgomeryParams;
+use cuint::secret::SecretUint;
+use cuint::util::Limb;
+
+use common::{random_uint, run_test_helper};
+
+fn test_core<T: Limb>(len: usize) {
+    let a = random_uint::<T>(len);
+    let c = a.sqr_();
+    assert_eq!(a.mul_(&a), c, "{}", len);
+    assert_eq!(2 * a.bit_width(), c.bit_width());
+}
+
+#[test]
+fn test_all_lengths() {
+    // Covers schoolbook, Karatsuba and Toom-3 squaring.
+    for len in 0..=300 {
+        test_core::<u16>(len);
+        test_core::<u32>(len);
+        test_core::<u64>(len);
+    }
+}
+
+#[test]
+fn test_extreme_values() {
+    for len in [1, 5, 40, 130, 200] {
+        let max = Uint::<u32>::from_le_bytes(&vec![0xff; 4 * len]).unwrap();
+        let expected = run_test_helper(&["mul", &max.to_str(), &max.to_str()]);
+        assert_eq!(expected, max.sqr_().to_str());
+    }
+    let zero = Uint::<u64>::from_le_bytes(&[0; 800]).unwrap();
+    assert_eq!("0x0", zero.sqr_().to_str());
+}
+
+#[test]
+fn test_fixed() {
+    for _ in 0..100 {
+        let a = FixedUint::<5, u32>::from_str(&random_uint::<u32>(5).to_str()).unwrap();
+        assert_eq!(a.mul_(&a), a.sqr_());
+        let b = FixedUint::<3, u64>::from_str(&random_uint::<u64>(2).to_str()).unwrap();
+        assert_eq!(b.mul_(&b), b.sqr_());
+    }
+    let max = FixedUint::<4, u16>::from_str("0xffffffffffffffff").unwrap();
+    assert_eq!(max.mul_(&max), max.sqr_());
+}
+
+#[test]
+fn test_secret() {
+    let a = random_uint::<u32>(20);
+    let secret = SecretUint::new(a.clone());
+    assert_eq!(&a.mul_(&a), secret.sqr_().expose_secret());
+}
+
+#[test]
+fn test_mont_sqr() {
+    for len in 1..40 {
+        let mut bytes = random_uint::<u64>(len).to_le_bytes();
+        bytes[0] |= 1;
+        let m = Uint::<u64>::from_le_bytes(&bytes).unwrap();
+        let params = MontgomeryParams::new(&m).unwrap();
+        let a = params.to_montgomery(&random_uint::<u64>(len));
+        assert_eq!(params.mont_mul(&a, &a), params.mont_sqr(&a), "{}", len);
+
+        // Largest reduced value, so the final subtraction is exercised.
+        let (max, _) = m.sub_(&Uint::<u64>::from_str("0x1").unwrap());
+        assert_eq!(params.mont_mul(&max, &max), params.mont_sqr(&max));
+    }
+}
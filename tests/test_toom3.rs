@@ -0,0 +1,74 @@
+extern crate cuint;
+extern crate rand;
+
+mod common;
+
+// Trait with all the functions.
+use cuint::base::{Uint, UintTrait};
+use cuint::uint::TOOM3_THRESHOLD;
+use cuint::util::Limb;
+
+use common::{random_uint, run_test_helper};
+
+/// A Uint<T> with `limbs` limbs that are all `byte`.
+fn filled_uint<T: Limb>(limbs: usize, byte: u8) -> Uint<T> {
+    Uint::<T>::from_le_bytes(&vec![byte; T::BITS / 8 * limbs]).unwrap()
+}
+
+/// The schoolbook product.
+fn schoolbook<T: Limb>(a: &Uint<T>, b: &Uint<T>) -> Uint<T> {
+    a.mul_karatsuba_(b, usize::MAX)
+}
+
+fn test_core<T: Limb>(a: &Uint<T>, b: &Uint<T>) {
+    let expected = schoolbook(a, b);
+    for threshold in [3, 40, 100, TOOM3_THRESHOLD] {
+        assert_eq!(expected, a.mul_toom3_(b, threshold));
+        assert_eq!(expected, b.mul_toom3_(a, threshold));
+    }
+    assert_eq!(expected, a.mul_(b));
+}
+
+#[test]
+fn test_all_lengths() {
+    for len in (1..=300).step_by(3) {
+        test_core(&random_uint::<u16>(len), &random_uint::<u16>(len));
+        test_core(&random_uint::<u32>(len + 1), &random_uint::<u32>(len + 1));
+        test_core(&random_uint::<u64>(len + 2), &random_uint::<u64>(len + 2));
+    }
+}
+
+#[test]
+fn test_unbalanced() {
+    for (len_a, len_b) in [(100, 67), (100, 68), (129, 128), (200, 150), (300, 40)] {
+        test_core(&random_uint::<u32>(len_a), &random_uint::<u32>(len_b));
+        test_core(&random_uint::<u64>(len_a), &random_uint::<u64>(len_b));
+    }
+}
+
+#[test]
+fn test_extreme_values() {
+    // All limbs set gives the largest evaluations, a zero middle part the
+    // most negative ones.
+    for len in [33, 96, 150] {
+        let max = filled_uint::<u16>(len, 0xff);
+        let expected = run_test_helper(&["mul", &max.to_str(), &max.to_str()]);
+        assert_eq!(expected, max.mul_toom3_(&max, 3).to_str());
+
+        let k = len.div_ceil(3);
+        let mut bytes = vec![0xff; 2 * len];
+        for b in bytes[2 * k..4 * k].iter_mut() {
+            *b = 0;
+        }
+        let x = Uint::<u16>::from_le_bytes(&bytes).unwrap();
+        let expected = run_test_helper(&["mul", &x.to_str(), &max.to_str()]);
+        assert_eq!(expected, x.mul_toom3_(&max, 3).to_str());
+        assert_eq!(expected, max.mul_toom3_(&x, 3).to_str());
+        test_core(&x, &x);
+    }
+
+    let zero = filled_uint::<u64>(200, 0);
+    let a = random_uint::<u64>(200);
+    assert_eq!("0x0", a.mul_toom3_(&zero, 3).to_str());
+    assert_eq!(400, a.mul_toom3_(&zero, 3).bit_width() / 64);
+}
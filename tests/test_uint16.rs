@@ -0,0 +1,256 @@
+extern crate cuint;
+
+use std::str::FromStr;
+// Trait with all the functions.
+use cuint::base::{Uint, UintTrait};
+use cuint::fixed::FixedUint;
+
+// With 16-bit limbs, small inputs already span several limbs, so testing all
+// of them exercises the carry handling of the shared algorithms.
+
+fn to_uint(x: u64) -> Uint<u16> {
+    Uint::<u16>::from_str(&format!("{:#x}", x)).unwrap()
+}
+
+fn to_u64<T: UintTrait>(x: &T) -> u64 {
+    u64::from_str_radix(&x.to_str()[2..], 16).unwrap()
+}
+
+/// Interesting values around the limb boundaries.
+fn edge_values() -> Vec<u64> {
+    let mut values = vec![];
+    for base in [0u64, 1 << 16, 1 << 32] {
+        for d in 0..4 {
+            values.push(base + d);
+            if base > d {
+                values.push(base - d - 1);
+            }
+        }
+    }
+    values.push(0xffff_ffff_ffff);
+    values
+}
+
+#[test]
+fn test_add_mul_exhaustive() {
+    for a in 0..0x200u64 {
+        for b in 0..0x200u64 {
+            let x = to_uint(a << 7);
+            let y = to_uint(b * 0x81);
+            assert_eq!((a << 7) + b * 0x81, to_u64(&(&x + &y)));
+            assert_eq!((a << 7) * (b * 0x81), to_u64(&(&x * &y)));
+        }
+    }
+    for &a in edge_values().iter() {
+        for &b in edge_values().iter() {
+            let (x, y) = (to_uint(a), to_uint(b));
+            assert_eq!(a + b, to_u64(&(&x + &y)));
+            if a < (1 << 32) && b < (1 << 32) {
+                assert_eq!(a * b, to_u64(&(&x * &y)));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_sub_exhaustive() {
+    fn check(a: u64, b: u64) {
+        // The difference wraps around at the length of the longer operand.
+        let limbs = |v: u64| std::cmp::max(1, (64 - v.leading_zeros()).div_ceil(16));
+        let bits = 16 * std::cmp::max(limbs(a), limbs(b));
+        let (x, y) = (to_uint(a), to_uint(b));
+        let (c, borrow) = x.sub_(&y);
+        assert_eq!(u16::from(a < b), borrow);
+        assert_eq!(a.wrapping_sub(b) & ((1 << bits) - 1), to_u64(&c));
+        assert_eq!(c, &x - &y);
+    }
+
+    for a in 0..0x200u64 {
+        for b in 0..0x200u64 {
+            check(a * 0x81, b << 7);
+        }
+    }
+    for &a in edge_values().iter() {
+        for &b in edge_values().iter() {
+            check(a, b);
+        }
+    }
+}
+
+#[test]
+fn test_ct_cmp_exhaustive() {
+    fn check(a: u64, b: u64, pad: usize) {
+        let x = to_uint(a);
+        // Leading zeroes add limbs without changing the value.
+        let y = Uint::<u16>::from_str(&format!("0x{:0width$x}", b, width = pad)).unwrap();
+        assert_eq!(u16::from(a == b), x.ct_eq(&y));
+        assert_eq!(u16::from(a == b), y.ct_eq(&x));
+        assert_eq!(u16::from(a < b), x.ct_lt(&y));
+        assert_eq!(u16::from(a > b), x.ct_gt(&y));
+        assert_eq!(u16::from(a >= b), x.ct_gte(&y));
+    }
+
+    for a in 0..0x100u64 {
+        for b in 0..0x100u64 {
+            check(a * 0x101, b * 0x101, 1);
+            check(a * 0x101, b * 0x101, 12);
+        }
+    }
+    for &a in edge_values().iter() {
+        for &b in edge_values().iter() {
+            check(a, b, 1);
+            check(a, b, 16);
+        }
+    }
+}
+
+#[test]
+fn test_radix_exhaustive() {
+    fn check<X: UintTrait + std::fmt::Debug>(a: u64) {
+        let x = X::from_str_radix(&format!("{:x}", a), 16).unwrap();
+        assert_eq!(format!("{}", a), x.to_str_radix(10));
+        assert_eq!(format!("{:b}", a), x.to_str_radix(2));
+        assert_eq!(format!("{:o}", a), x.to_str_radix(8));
+        assert_eq!(format!("{:x}", a), x.to_str_radix(16));
+        for s in [format!("{}", a), format!("0b{:b}", a), format!("0o{:o}", a)] {
+            let y = s.parse::<X>().ok().unwrap();
+            assert_eq!(a, to_u64(&y));
+        }
+    }
+
+    for a in 0..0x20000u64 {
+        check::<Uint<u16>>(a * 0x81);
+    }
+    for &a in edge_values().iter() {
+        check::<Uint<u16>>(a);
+        check::<FixedUint<3, u16>>(a);
+    }
+}
+
+#[test]
+fn test_fmt_exhaustive() {
+    fn check<
+        X: UintTrait
+            + std::fmt::Display
+            + std::fmt::LowerHex
+            + std::fmt::UpperHex
+            + std::fmt::Octal
+            + std::fmt::Binary,
+    >(
+        x: &X,
+        a: u64,
+    ) {
+        assert_eq!(format!("{}", a), format!("{}", x));
+        assert_eq!(format!("{:x}", a), format!("{:x}", x));
+        assert_eq!(format!("{:X}", a), format!("{:X}", x));
+        assert_eq!(format!("{:o}", a), format!("{:o}", x));
+        assert_eq!(format!("{:b}", a), format!("{:b}", x));
+        assert_eq!(format!("{:#x}", a), format!("{:#x}", x));
+        assert_eq!(format!("{:#X}", a), format!("{:#X}", x));
+        assert_eq!(format!("{:#o}", a), format!("{:#o}", x));
+        assert_eq!(format!("{:#b}", a), format!("{:#b}", x));
+        assert_eq!(format!("{:#066x}", a), format!("{:#066x}", x));
+        assert_eq!(format!("{:020}", a), format!("{:020}", x));
+        assert_eq!(format!("{:+}", a), format!("{:+}", x));
+        assert_eq!(format!("{:>25}|", a), format!("{:>25}|", x));
+        assert_eq!(format!("{:*<25}|", a), format!("{:*<25}|", x));
+        assert_eq!(format!("{:^#25x}|", a), format!("{:^#25x}|", x));
+        assert_eq!(format!("{:3}", a), format!("{:3}", x));
+    }
+
+    for a in 0..0x4000u64 {
+        let a = a * 0x40f;
+        check(&to_uint(a), a);
+    }
+    for &a in edge_values().iter() {
+        check(&to_uint(a), a);
+        check(
+            &FixedUint::<3, u16>::from_str(&format!("{:#x}", a)).unwrap(),
+            a,
+        );
+        // Leading zero limbs are not printed.
+        check(
+            &FixedUint::<8, u16>::from_str(&format!("{:#x}", a)).unwrap(),
+            a,
+        );
+    }
+}
+
+#[test]
+fn test_mod_exhaustive() {
+    for a in 0..0x400u64 {
+        for m in 1..0x100u64 {
+            let (x, z) = (to_uint(a * 0x101), to_uint(m));
+            assert_eq!((a * 0x101) % m, to_u64(&(&x % &z)));
+        }
+    }
+    for &a in edge_values().iter() {
+        for &m in edge_values().iter().filter(|&&m| m != 0) {
+            let (x, z) = (to_uint(a), to_uint(m));
+            assert_eq!(a % m, to_u64(&(&x % &z)));
+        }
+    }
+}
+
+#[test]
+fn test_mod_ops_exhaustive() {
+    for m in 1..0x40u64 {
+        let z = to_uint(m);
+        for a in 0..m {
+            for b in 0..m {
+                let (x, y) = (to_uint(a), to_uint(b));
+                assert_eq!((a + b) % m, to_u64(&x.add_mod_(&y, &z)));
+                assert_eq!((a * b) % m, to_u64(&x.mul_mod_(&y, &z)));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_pow_mod_exhaustive() {
+    fn pow_mod(a: u64, mut e: u64, m: u64) -> u64 {
+        let mut r = 1 % m;
+        let mut a = a % m;
+        while e > 0 {
+            if e & 1 == 1 {
+                r = r * a % m;
+            }
+            a = a * a % m;
+            e >>= 1;
+        }
+        r
+    }
+
+    for m in [1u64, 2, 3, 0xffff, 0x10001, 0xfffffffb] {
+        let z = to_uint(m);
+        for a in 0..40u64 {
+            for e in 0..40u64 {
+                let x = to_uint(a * 0x1234567);
+                let y = to_uint(e * 0x10001);
+                let expected = pow_mod(a * 0x1234567, e * 0x10001, m);
+                assert_eq!(expected, to_u64(&x.pow_mod_(&y, &z)));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_fixed_u16() {
+    for a in 0..0x100u64 {
+        for b in 0..0x100u64 {
+            let x = FixedUint::<2, u16>::from_str(&format!("{:#x}", a * 0x1ff)).unwrap();
+            let y = FixedUint::<2, u16>::from_str(&format!("{:#x}", b * 0x3fff)).unwrap();
+            let expected = (a * 0x1ff + b * 0x3fff) & 0xffff_ffff;
+            assert_eq!(expected, to_u64(&(x + y)));
+            let expected = (a * 0x1ff * b * 0x3fff) & 0xffff_ffff;
+            assert_eq!(expected, to_u64(&(x * y)));
+            let (c, borrow) = x.sub_(&y);
+            let expected = (a * 0x1ff).wrapping_sub(b * 0x3fff) & 0xffff_ffff;
+            assert_eq!(expected, to_u64(&c));
+            assert_eq!(u16::from(a * 0x1ff < b * 0x3fff), borrow);
+            assert_eq!(c, x - y);
+            assert_eq!(u16::from(a * 0x1ff == b * 0x3fff), x.ct_eq(&y));
+            assert_eq!(u16::from(a * 0x1ff >= b * 0x3fff), x.ct_gte(&y));
+        }
+    }
+}
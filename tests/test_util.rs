@@ -37,6 +37,27 @@ fn test_equal() {
     test_inner(0xFFFFFFFFFFFFFFFFu64, 0xFFFFFFFFFFFFFFFFu64, 1u64);
     test_inner(0xFFFFFFFFFFFFFFFFu64, 0xFFFFFFFFFFFFFFFEu64, 0u64);
     test_inner(0xEFFFFFFFFFFFFFFFu64, 0xFFFFFFFFFFFFFFFFu64, 0u64);
+
+    test_inner(0u128, 0u128, 1u128);
+    test_inner(123u128, 123u128, 1u128);
+    test_inner(123u128, 124u128, 0u128);
+    test_inner(124u128, 123u128, 0u128);
+    test_inner(
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        1u128,
+    );
+    test_inner(
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEu128,
+        0u128,
+    );
+    test_inner(
+        0xEFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        0u128,
+    );
+    test_inner(1u128 << 64, 0u128, 0u128);
 }
 
 #[test]
@@ -72,6 +93,28 @@ fn test_gte() {
     test_inner(0xFFFFFFFFFFFFFFFFu64, 0xFFFFFFFFFFFFFFFFu64, 1u64);
     test_inner(0xFFFFFFFFFFFFFFFFu64, 0xFFFFFFFFFFFFFFFEu64, 1u64);
     test_inner(0xEFFFFFFFFFFFFFFFu64, 0xFFFFFFFFFFFFFFFFu64, 0u64);
+
+    test_inner(0u128, 0u128, 1u128);
+    test_inner(123u128, 123u128, 1u128);
+    test_inner(123u128, 124u128, 0u128);
+    test_inner(124u128, 123u128, 1u128);
+    test_inner(
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        1u128,
+    );
+    test_inner(
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEu128,
+        1u128,
+    );
+    test_inner(
+        0xEFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        0u128,
+    );
+    test_inner(0u128, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128, 0u128);
+    test_inner(0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128, 0u128, 1u128);
 }
 
 #[test]
@@ -144,6 +187,25 @@ fn test_add() {
         (0xFFFFFFFFFFFFFFFEu64, 1u64),
     );
 
+    test_inner(0u128, 0u128, 0u128, (0u128, 0u128), (0u128, 0u128));
+    test_inner(0u128, 0u128, 1u128, (0u128, 0u128), (0u128, 0u128));
+    test_inner(456u128, 123u128, 0u128, (456u128, 0u128), (579u128, 0u128));
+    test_inner(456u128, 123u128, 1u128, (579u128, 0u128), (579u128, 0u128));
+    test_inner(
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        0u128,
+        (0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128, 0u128),
+        (0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEu128, 1u128),
+    );
+    test_inner(
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        1u128,
+        (0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEu128, 1u128),
+        (0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEu128, 1u128),
+    );
+
     test_inner(
         0xd77f530au32,
         0xa67ba0d3u32,
@@ -153,6 +215,168 @@ fn test_add() {
     );
 }
 
+#[test]
+fn test_sub() {
+    fn test_inner<T>(a: T, b: T, c: T, expected_c: (T, T), expected: (T, T))
+    where
+        T: PartialEq + std::fmt::Debug + CTimeOperations<T>,
+    {
+        let x = T::csub(&a, &b, &c);
+        let (r1, r2) = T::sub_with_borrow(&a, &b);
+        println!("{:x?} - {:x?} => {:x?}", a, b, x);
+        println!("{:x?} - {:x?} => {:x?} | {:x?}", a, b, r2, r1);
+        assert_eq!(expected_c, x);
+        assert_eq!(expected, (r1, r2));
+    }
+    test_inner(0u16, 0u16, 0u16, (0u16, 0u16), (0u16, 0u16));
+    test_inner(0u16, 0u16, 1u16, (0u16, 0u16), (0u16, 0u16));
+    test_inner(456u16, 123u16, 0u16, (456u16, 0u16), (333u16, 0u16));
+    test_inner(456u16, 123u16, 1u16, (333u16, 0u16), (333u16, 0u16));
+    test_inner(
+        123u16,
+        456u16,
+        0u16,
+        (123u16, 0u16),
+        (0xFFFFu16 - 332u16, 1u16),
+    );
+    test_inner(
+        123u16,
+        456u16,
+        1u16,
+        (0xFFFFu16 - 332u16, 1u16),
+        (0xFFFFu16 - 332u16, 1u16),
+    );
+    test_inner(0u16, 1u16, 1u16, (0xFFFFu16, 1u16), (0xFFFFu16, 1u16));
+    test_inner(
+        0xFFFEu16,
+        0xFFFFu16,
+        1u16,
+        (0xFFFFu16, 1u16),
+        (0xFFFFu16, 1u16),
+    );
+    test_inner(0xFFFFu16, 0xFFFFu16, 1u16, (0u16, 0u16), (0u16, 0u16));
+
+    test_inner(0u32, 0u32, 0u32, (0u32, 0u32), (0u32, 0u32));
+    test_inner(0u32, 0u32, 1u32, (0u32, 0u32), (0u32, 0u32));
+    test_inner(456u32, 123u32, 0u32, (456u32, 0u32), (333u32, 0u32));
+    test_inner(456u32, 123u32, 1u32, (333u32, 0u32), (333u32, 0u32));
+    test_inner(
+        123u32,
+        456u32,
+        0u32,
+        (123u32, 0u32),
+        (0xFFFFFFFFu32 - 332u32, 1u32),
+    );
+    test_inner(
+        123u32,
+        456u32,
+        1u32,
+        (0xFFFFFFFFu32 - 332u32, 1u32),
+        (0xFFFFFFFFu32 - 332u32, 1u32),
+    );
+    test_inner(
+        0u32,
+        1u32,
+        1u32,
+        (0xFFFFFFFFu32, 1u32),
+        (0xFFFFFFFFu32, 1u32),
+    );
+    test_inner(
+        0xFFFFFFFEu32,
+        0xFFFFFFFFu32,
+        1u32,
+        (0xFFFFFFFFu32, 1u32),
+        (0xFFFFFFFFu32, 1u32),
+    );
+    test_inner(
+        0xFFFFFFFFu32,
+        0xFFFFFFFFu32,
+        1u32,
+        (0u32, 0u32),
+        (0u32, 0u32),
+    );
+
+    test_inner(0u64, 0u64, 0u64, (0u64, 0u64), (0u64, 0u64));
+    test_inner(0u64, 0u64, 1u64, (0u64, 0u64), (0u64, 0u64));
+    test_inner(456u64, 123u64, 0u64, (456u64, 0u64), (333u64, 0u64));
+    test_inner(456u64, 123u64, 1u64, (333u64, 0u64), (333u64, 0u64));
+    test_inner(
+        123u64,
+        456u64,
+        0u64,
+        (123u64, 0u64),
+        (0xFFFFFFFFFFFFFFFFu64 - 332u64, 1u64),
+    );
+    test_inner(
+        123u64,
+        456u64,
+        1u64,
+        (0xFFFFFFFFFFFFFFFFu64 - 332u64, 1u64),
+        (0xFFFFFFFFFFFFFFFFu64 - 332u64, 1u64),
+    );
+    test_inner(
+        0u64,
+        1u64,
+        1u64,
+        (0xFFFFFFFFFFFFFFFFu64, 1u64),
+        (0xFFFFFFFFFFFFFFFFu64, 1u64),
+    );
+    test_inner(
+        0xFFFFFFFFFFFFFFFEu64,
+        0xFFFFFFFFFFFFFFFFu64,
+        1u64,
+        (0xFFFFFFFFFFFFFFFFu64, 1u64),
+        (0xFFFFFFFFFFFFFFFFu64, 1u64),
+    );
+    test_inner(
+        0xFFFFFFFFFFFFFFFFu64,
+        0xFFFFFFFFFFFFFFFFu64,
+        1u64,
+        (0u64, 0u64),
+        (0u64, 0u64),
+    );
+
+    test_inner(0u128, 0u128, 0u128, (0u128, 0u128), (0u128, 0u128));
+    test_inner(0u128, 0u128, 1u128, (0u128, 0u128), (0u128, 0u128));
+    test_inner(456u128, 123u128, 0u128, (456u128, 0u128), (333u128, 0u128));
+    test_inner(456u128, 123u128, 1u128, (333u128, 0u128), (333u128, 0u128));
+    test_inner(
+        123u128,
+        456u128,
+        0u128,
+        (123u128, 0u128),
+        (0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128 - 332u128, 1u128),
+    );
+    test_inner(
+        123u128,
+        456u128,
+        1u128,
+        (0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128 - 332u128, 1u128),
+        (0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128 - 332u128, 1u128),
+    );
+    test_inner(
+        0u128,
+        1u128,
+        1u128,
+        (0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128, 1u128),
+        (0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128, 1u128),
+    );
+    test_inner(
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEu128,
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        1u128,
+        (0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128, 1u128),
+        (0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128, 1u128),
+    );
+    test_inner(
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        1u128,
+        (0u128, 0u128),
+        (0u128, 0u128),
+    );
+}
+
 #[test]
 fn test_cmul() {
     fn test_inner<T>(a: T, b: T, c: T, expected_c: (T, T), expected: (T, T))
@@ -222,6 +446,64 @@ fn test_cmul() {
         (0x0000000000000001u64, 0xfffffffffffffffeu64),
         (0x0000000000000001u64, 0xfffffffffffffffeu64),
     );
+
+    test_inner(0u128, 0u128, 0u128, (0u128, 0u128), (0u128, 0u128));
+    test_inner(0u128, 0u128, 1u128, (0u128, 0u128), (0u128, 0u128));
+    test_inner(
+        456u128,
+        123u128,
+        0u128,
+        (456u128, 0u128),
+        (0xdb18u128, 0u128),
+    );
+    test_inner(
+        456u128,
+        123u128,
+        1u128,
+        (0xdb18u128, 0u128),
+        (0xdb18u128, 0u128),
+    );
+    test_inner(
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        0u128,
+        (0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128, 0u128),
+        (1u128, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEu128),
+    );
+    test_inner(
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        1u128,
+        (1u128, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEu128),
+        (1u128, 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEu128),
+    );
+    test_inner(
+        1u128 << 64,
+        1u128 << 64,
+        1u128,
+        (0u128, 1u128),
+        (0u128, 1u128),
+    );
+    test_inner(
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        2u128,
+        1u128,
+        (0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEu128, 1u128),
+        (0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEu128, 1u128),
+    );
+    test_inner(
+        0xd77f530a9c3e1b256f0e4d2ab1c3e5f7u128,
+        0xa67ba0d3123456789abcdef00fedcba9u128,
+        1u128,
+        (
+            0xa99be795cd8ec9b3990aa28b1058ad0fu128,
+            0x8c24a16708946d54d221944858036046u128,
+        ),
+        (
+            0xa99be795cd8ec9b3990aa28b1058ad0fu128,
+            0x8c24a16708946d54d221944858036046u128,
+        ),
+    );
 }
 
 #[test]
@@ -287,9 +569,38 @@ fn test_cswap() {
         1u64,
         (0x12345678deadbeefu64, 0xFFFFFFFFFFFFFFFFu64),
     );
+
+    test_inner(0u128, 0u128, 0u128, (0u128, 0u128));
+    test_inner(0u128, 0u128, 1u128, (0u128, 0u128));
+    test_inner(456u128, 123u128, 0u128, (456u128, 123u128));
+    test_inner(456u128, 123u128, 1u128, (123u128, 456u128));
+    test_inner(
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        1u128,
+        (
+            0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+            0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        ),
+    );
+    test_inner(
+        0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        0x12345678deadbeef0123456789abcdefu128,
+        1u128,
+        (
+            0x12345678deadbeef0123456789abcdefu128,
+            0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu128,
+        ),
+    );
 }
 
 #[test]
+#[allow(
+    clippy::needless_return,
+    clippy::legacy_numeric_constants,
+    clippy::assign_op_pattern,
+    clippy::useless_vec
+)]
 fn test_overflowing_add_timings() {
     fn overflowing_add_time_inner(x: u32, y: u32) -> i64 {
         let start = PreciseTime::now();
@@ -0,0 +1,70 @@
+extern crate cuint;
+
+use std::mem::{size_of, MaybeUninit};
+use std::ptr;
+use std::slice;
+use std::str::FromStr;
+// Trait with all the functions.
+use cuint::base::{Uint, UintTrait};
+use cuint::fixed::FixedUint;
+use cuint::secret::SecretUint;
+
+const KEY: &str = "0xdeadbeefcafebabe0123456789abcdef";
+
+#[test]
+fn test_clear() {
+    let mut x = Uint::<u32>::from_str(KEY).unwrap();
+    x.clear();
+    assert_eq!("0x0", x.to_str());
+    assert_eq!(0, x.bit_width());
+    assert_eq!(Uint::<u32>::default(), x);
+
+    // The cleared Uint can be used again.
+    let y = Uint::<u32>::from_str(KEY).unwrap();
+    x.conditional_assign(&y, 1);
+    assert_eq!(y, x);
+
+    let mut x = FixedUint::<4, u64>::from_str(KEY).unwrap();
+    x.clear();
+    assert_eq!("0x0", x.to_str());
+    assert_eq!(256, x.bit_width());
+
+    let mut x = SecretUint::new(Uint::<u16>::from_str(KEY).unwrap());
+    x.clear();
+    assert_eq!("0x0", x.expose_secret().to_str());
+}
+
+#[test]
+fn test_drop_fixed() {
+    // FixedUint is Copy, so it's wiped on drop through SecretUint.
+    let x = SecretUint::new(FixedUint::<4, u64>::from_str(KEY).unwrap());
+    let mut x = MaybeUninit::new(x);
+    // Drop the value in place and look at the memory it occupied.
+    let bytes = unsafe {
+        ptr::drop_in_place(x.as_mut_ptr());
+        slice::from_raw_parts(
+            x.as_ptr() as *const u8,
+            size_of::<SecretUint<FixedUint<4, u64>>>(),
+        )
+    };
+    assert!(bytes.iter().all(|&b| b == 0), "{:x?}", bytes);
+}
+
+#[test]
+fn test_resize_keeps_value() {
+    // Growing a Uint moves the limbs into a new allocation.
+    let a = Uint::<u32>::from_str(KEY).unwrap();
+    let b = Uint::<u32>::from_str(&format!("{}{}", KEY, "0".repeat(100))).unwrap();
+    let (mut x, mut y) = (a.clone(), b.clone());
+    Uint::conditional_swap(&mut x, &mut y, 1);
+    assert_eq!(1, x.ct_eq(&b));
+    assert_eq!(1, y.ct_eq(&a));
+    Uint::conditional_swap(&mut x, &mut y, 0);
+    assert_eq!(1, x.ct_eq(&b));
+    assert_eq!(1, y.ct_eq(&a));
+
+    let mut x = a.clone();
+    x.conditional_assign(&b, 0);
+    assert_eq!(1, x.ct_eq(&a));
+    assert_eq!(b.bit_width(), x.bit_width());
+}